@@ -6,7 +6,10 @@ use rand::Rng;
 use rand::prelude::IndexedRandom;
 use rayon::prelude::*;
 use sat_puzzles::find_all_solutions;
-use sat_puzzles::minesweeper_sat::{decode_solution, generate_clauses};
+use sat_puzzles::minesweeper_sat::{
+    ComponentCache, combine_components, combinations, decompose_and_solve, decode_solution,
+    deduce_certain_cells, generate_clauses, propagate_trivial_deductions,
+};
 use std::collections::HashSet;
 use std::path::PathBuf;
 
@@ -30,21 +33,7 @@ struct Cli {
     cnf_file: Option<PathBuf>,
 }
 
-/// Calculates combinations "n choose k" using u128 to prevent overflow.
-fn combinations(n: u128, k: u128) -> u128 {
-    if k > n {
-        return 0;
-    }
-    if k == 0 || k == n {
-        return 1;
-    }
-    if k > n / 2 {
-        return combinations(n, n - k);
-    }
-    (k + 1..=n).fold(1, |acc, val| acc * val / (val - k))
-}
-
-fn calculate_mine_probs(game: &Game) -> Vec<f64> {
+fn calculate_mine_probs(game: &Game, cache: &mut ComponentCache) -> Vec<f64> {
     let (global_constraint, local_constraints, sea_of_unknown) = game.get_constraints();
     let sea_set: HashSet<_> = sea_of_unknown.into_iter().collect();
 
@@ -54,32 +43,23 @@ fn calculate_mine_probs(game: &Game) -> Vec<f64> {
         .filter(|index| !sea_set.contains(index))
         .collect();
 
-    let (clauses, var_map) = generate_clauses(&unknown_indices, &local_constraints);
     game.display_all();
     println!("{game}");
-    sat_puzzles::write_clauses("minesweeper.cnf", &clauses);
-    let sat_iterator = find_all_solutions(&clauses).unwrap();
+
+    // Solve each connected border component independently and recombine the per-component
+    // mine-count distributions via convolution, instead of enumerating every combination of
+    // every constraint in one shot.
+    let components = decompose_and_solve(&unknown_indices, &local_constraints, cache);
+    let (cell_weight, sea_cell_weight, total_weight) =
+        combine_components(&components, sea_set.len(), global_constraint.count as usize);
 
     let n_cells = game.width * game.height;
-    let mut total_weight = 0.0;
     let mut probs = vec![0.0; n_cells];
-    for model in sat_iterator {
-        let solution = decode_solution(&model, game.width, game.height, &var_map);
-        let local_mines = solution.mines.iter().filter(|&&b| b).count();
-        let remaining_mines = global_constraint.count - local_mines as f64;
-        let weight = combinations(sea_set.len() as u128, remaining_mines as u128) as f64;
-        total_weight += weight;
-
-        let prob_contribution = weight;
-        for (i, &is_mine) in solution.mines.iter().enumerate() {
-            if is_mine {
-                probs[i] += prob_contribution;
-            }
-        }
-        let sea_prob = remaining_mines / sea_set.len() as f64;
-        for &idx in &sea_set {
-            probs[idx] += sea_prob * prob_contribution;
-        }
+    for (&idx, &weight) in &cell_weight {
+        probs[idx] = weight;
+    }
+    for &idx in &sea_set {
+        probs[idx] = sea_cell_weight;
     }
     probs.iter_mut().for_each(|p| *p /= total_weight);
     probs
@@ -93,7 +73,12 @@ fn benchmark_solver(
     first_click: Option<(usize, usize)>,
 ) -> usize {
     let (width, height, num_mines) = difficulty.dimensions();
-    (0..num_games)
+    // Shared across every game in this run, so components with the same structure (which
+    // recur often both within a game and across games) are solved only once. This is why
+    // `.into_par_iter()` below stays commented out: sharing `&mut cache` across games isn't
+    // safe without wrapping it in a `Mutex`.
+    let mut cache = ComponentCache::new();
+    let wins: usize = (0..num_games)
         .into_iter()
         //.into_par_iter()
         .enumerate()
@@ -108,7 +93,41 @@ fn benchmark_solver(
 
             while game.state == GameState::Playing {
                 println!("Game {game_number}");
-                let probs = calculate_mine_probs(&game);
+
+                // Cheaply settle any cell whose status is logically forced before paying
+                // for the full probability computation.
+                let (global_constraint, local_constraints, sea_of_unknown) =
+                    game.get_constraints();
+                let sea_set: HashSet<_> = sea_of_unknown.into_iter().collect();
+                let unknown_indices: Vec<usize> = global_constraint
+                    .cells
+                    .into_iter()
+                    .filter(|index| !sea_set.contains(index))
+                    .collect();
+
+                // Cheapest tier first: pure constraint-counting propagation, no SAT call.
+                let propagation = propagate_trivial_deductions(&unknown_indices, &local_constraints);
+                if !propagation.safe.is_empty() {
+                    for idx in propagation.safe {
+                        game.reveal(idx % width, idx / width);
+                    }
+                    continue;
+                }
+
+                // Next-cheapest tier: incremental SAT-based deduction over what propagation
+                // couldn't settle on its own.
+                let (safe_cells, _mine_cells) = deduce_certain_cells(
+                    &propagation.residual_unknowns,
+                    &propagation.residual_constraints,
+                );
+                if !safe_cells.is_empty() {
+                    for idx in safe_cells {
+                        game.reveal(idx % width, idx / width);
+                    }
+                    continue;
+                }
+
+                let probs = calculate_mine_probs(&game, &mut cache);
 
                 // Find lowest probability among covered cells
                 let mut min_prob = f64::INFINITY;
@@ -145,7 +164,14 @@ fn benchmark_solver(
 
             (game.state == GameState::Won) as usize
         })
-        .sum()
+        .sum();
+
+    let stats = cache.stats();
+    println!(
+        "Component cache: {} hits, {} misses",
+        stats.hits, stats.misses
+    );
+    wins
 }
 
 fn main_bench() {
@@ -194,7 +220,29 @@ fn main_cli() -> Result<()> {
         .filter(|index| !sea_set.contains(index))
         .collect();
 
-    let (clauses, var_map) = generate_clauses(&unknown_indices, &local_constraints);
+    // Cheaply settle any cell whose status is logically forced before paying for the full
+    // probability computation below. Pure constraint-counting propagation runs first (no SAT
+    // call at all); only what it leaves undetermined goes to the incremental SAT deduction,
+    // and only the residual constraints/cells ever reach `generate_clauses`.
+    let propagation = propagate_trivial_deductions(&unknown_indices, &local_constraints);
+    let (safe_cells, mine_cells) = deduce_certain_cells(
+        &propagation.residual_unknowns,
+        &propagation.residual_constraints,
+    );
+    let safe_cells: HashSet<usize> = safe_cells.union(&propagation.safe).copied().collect();
+    let mine_cells: HashSet<usize> = mine_cells.union(&propagation.mine).copied().collect();
+    if !safe_cells.is_empty() || !mine_cells.is_empty() {
+        println!(
+            "Deduction: {} cell(s) provably safe, {} cell(s) provably mines.",
+            safe_cells.len(),
+            mine_cells.len()
+        );
+    }
+
+    let (clauses, var_map) = generate_clauses(
+        &propagation.residual_unknowns,
+        &propagation.residual_constraints,
+    );
 
     if let Some(path) = &cli.cnf_file {
         sat_puzzles::write_clauses(path, &clauses)?;
@@ -211,7 +259,9 @@ fn main_cli() -> Result<()> {
     for model in sat_iterator {
         n_sat_solutions += 1;
         let solution = decode_solution(&model, game.width, game.height, &var_map);
-        let local_mines = solution.mines.iter().filter(|&&b| b).count();
+        // `solution` only covers the residual cells left after propagation; the cells
+        // propagation already forced to be mines count against the total just the same.
+        let local_mines = solution.mines.iter().filter(|&&b| b).count() + propagation.mine.len();
         let remaining_mines = global_constraint.count - local_mines as f64;
         remaining_mines_sum += remaining_mines;
         let weight = combinations(sea_set.len() as u128, remaining_mines as u128) as f64;
@@ -229,6 +279,14 @@ fn main_cli() -> Result<()> {
         }
     }
     probs.iter_mut().for_each(|p| *p /= total_weight);
+    // Cells propagation already settled are certain, independent of how the remaining SAT
+    // solutions distribute the rest of the mines.
+    for &idx in &propagation.mine {
+        probs[idx] = 1.0;
+    }
+    for &idx in &propagation.safe {
+        probs[idx] = 0.0;
+    }
 
     let remaining_mines_avg = if n_sat_solutions > 0 {
         remaining_mines_sum as u128 / n_sat_solutions
@@ -1,14 +1,21 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
-use sat_puzzles::{n_queens, sudoku};
-use std::collections::HashSet;
+use sat_puzzles::{map_colour, n_queens, sudoku};
 use std::fs::File;
 use std::io::{BufWriter, Write};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(author, version, about = "SAT-based puzzle solver collection")]
 #[command(propagate_version = true)]
 struct Cli {
+    /// Which solving strategy to enumerate solutions with: the SAT encoding (default), or a
+    /// dependency-free native backtracking search. The native search doesn't scale past small
+    /// encodings (e.g. it won't handle a full 9x9 Sudoku) and is rejected outright once a
+    /// puzzle gets too large for it
+    #[arg(long, global = true, default_value = "sat")]
+    engine: sat_puzzles::Engine,
+
     #[command(subcommand)]
     puzzle: PuzzleType,
 }
@@ -33,6 +40,11 @@ enum PuzzleType {
         #[command(subcommand)]
         command: MapColourCommand,
     },
+    /// Solve a raw DIMACS CNF file
+    Cnf {
+        #[command(subcommand)]
+        command: CnfCommand,
+    },
 }
 
 #[derive(Subcommand)]
@@ -58,15 +70,42 @@ enum SudokuCommand {
     List,
     /// Generate a DIMACS CNF file
     Generate {
-        /// Puzzle name (easy, harder, hard)
+        /// Puzzle name (easy, harder, hard), or an inline puzzle string if --from-file isn't
+        /// given (compact one-char-per-cell form, or "rows,cols" + "row,col,value" lines)
         #[arg(default_value = "easy")]
         puzzle: String,
+        /// Read the puzzle from a file instead of using `puzzle` as a preset name or inline
+        /// puzzle string
+        #[arg(short, long, value_name = "FILE")]
+        from_file: Option<PathBuf>,
+        /// Expected box size (side length is box_size^2) — e.g. 4 for a 16x16 grid. If given,
+        /// the puzzle is rejected unless it matches.
+        #[arg(short, long)]
+        box_size: Option<usize>,
     },
     /// Solve a specific puzzle
     Solve {
-        /// Puzzle name (easy, harder, hard)
+        /// Puzzle name (easy, harder, hard), or an inline puzzle string if --from-file isn't
+        /// given (compact one-char-per-cell form, or "rows,cols" + "row,col,value" lines)
         #[arg(default_value = "easy")]
         puzzle: String,
+        /// Read the puzzle from a file instead of using `puzzle` as a preset name or inline
+        /// puzzle string
+        #[arg(short, long, value_name = "FILE")]
+        from_file: Option<PathBuf>,
+        /// Expected box size (side length is box_size^2) — e.g. 4 for a 16x16 grid. If given,
+        /// the puzzle is rejected unless it matches.
+        #[arg(short, long)]
+        box_size: Option<usize>,
+    },
+    /// Generate a new puzzle with a unique solution
+    GeneratePuzzle {
+        /// Box size (side length is box_size^2) — e.g. 3 for a classic 9x9 grid
+        #[arg(short, long, default_value = "3")]
+        box_size: usize,
+        /// How few clues to aim for: easy, medium or hard
+        #[arg(short, long, default_value = "medium")]
+        difficulty: sat_puzzles::generator::Difficulty,
     },
 }
 
@@ -92,17 +131,40 @@ enum MapColourCommand {
     },
 }
 
-fn num_vars(clauses: &[Vec<isize>]) -> usize {
-    let mut set = HashSet::new();
-    for clause in clauses {
-        for &lit in clause {
-            set.insert(lit.abs());
+#[derive(Subcommand)]
+enum CnfCommand {
+    /// Solve a DIMACS CNF file, printing the SAT solver's verdict and model
+    Solve {
+        /// Path to the .cnf file
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+    },
+}
+
+fn handle_cnf(command: CnfCommand) -> Result<()> {
+    match command {
+        CnfCommand::Solve { file } => {
+            let clauses = sat_puzzles::read_clauses(&file)
+                .with_context(|| format!("failed to read DIMACS file {}", file.display()))?;
+            match sat_puzzles::find_all_solutions(&clauses)?.next() {
+                Some(model) => {
+                    println!("s SATISFIABLE");
+                    print!("v");
+                    for lit in model {
+                        print!(" {}", lit.to_dimacs());
+                    }
+                    println!(" 0");
+                }
+                None => println!("s UNSATISFIABLE"),
+            }
         }
     }
-    set.len()
+    Ok(())
 }
 
-fn handle_nqueens(command: NQueensCommand) -> Result<()> {
+fn handle_nqueens(command: NQueensCommand, _engine: sat_puzzles::Engine) -> Result<()> {
+    // n_queens doesn't yet have engine-aware solving entry points (see nqueens_sat/n_queens
+    // module wiring), so `_engine` is accepted for a consistent CLI surface but not used yet.
     match command {
         NQueensCommand::Generate { n } => {
             println!("Generating CNF for {n}-Queens problem...");
@@ -151,27 +213,63 @@ fn handle_nqueens(command: NQueensCommand) -> Result<()> {
 
 fn get_sudoku_grid(puzzle: &str) -> Option<sudoku::SudokuGrid> {
     match puzzle {
-        "easy" => Some(sudoku::PUZZLE_EASY),
-        "harder" => Some(sudoku::PUZZLE_HARDER),
-        "hard" => Some(sudoku::PUZZLE_HARD),
+        "easy" => Some(sudoku::puzzle_easy()),
+        "harder" => Some(sudoku::puzzle_harder()),
+        "hard" => Some(sudoku::puzzle_hard()),
         _ => None,
     }
 }
 
-fn handle_sudoku(command: SudokuCommand) -> Result<()> {
+/// Resolves a puzzle from `--from-file`, falling back to `puzzle` as a preset name, and
+/// finally trying to parse `puzzle` itself as an inline puzzle string. If `box_size` is given,
+/// the resolved puzzle is rejected unless its box size matches.
+fn resolve_sudoku_grid(
+    puzzle: &str,
+    from_file: &Option<PathBuf>,
+    box_size: Option<usize>,
+) -> Result<sudoku::SudokuGrid> {
+    let grid = if let Some(path) = from_file {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read puzzle file {}", path.display()))?;
+        sudoku::SudokuGrid::parse(&text)
+            .with_context(|| format!("failed to parse puzzle file {}", path.display()))?
+    } else if let Some(grid) = get_sudoku_grid(puzzle) {
+        grid
+    } else {
+        sudoku::SudokuGrid::parse(puzzle).with_context(|| {
+            format!("'{puzzle}' is not a known preset (easy, harder, hard) and could not be parsed as a puzzle")
+        })?
+    };
+
+    if let Some(expected) = box_size {
+        anyhow::ensure!(
+            grid.box_size() == expected,
+            "expected a box size of {expected} ({}x{} grid), but the puzzle has box size {} ({}x{})",
+            expected * expected,
+            expected * expected,
+            grid.box_size(),
+            grid.n(),
+            grid.n()
+        );
+    }
+    Ok(grid)
+}
+
+fn handle_sudoku(command: SudokuCommand, engine: sat_puzzles::Engine) -> Result<()> {
     match command {
         SudokuCommand::List => println!("easy, harder, hard"),
-        SudokuCommand::Generate { puzzle } => {
-            let Some(grid) = get_sudoku_grid(&puzzle) else {
-                println!("Can't find {puzzle} - try easy, harder or hard");
-                return Ok(());
-            };
+        SudokuCommand::Generate {
+            puzzle,
+            from_file,
+            box_size,
+        } => {
+            let grid = resolve_sudoku_grid(&puzzle, &from_file, box_size)?;
             println!("Generating CNF for {puzzle} Sudoku problem...");
             let clauses = sudoku::generate_clauses(&grid);
             let output = "sudoku.cnf";
             let file = File::create(output)?;
             let mut writer = BufWriter::new(file);
-            let num_vars = num_vars(&clauses);
+            let num_vars = sudoku::num_vars(grid.box_size());
 
             writeln!(writer, "p cnf {num_vars} {}", clauses.len())?;
             for clause in &clauses {
@@ -187,16 +285,17 @@ fn handle_sudoku(command: SudokuCommand) -> Result<()> {
                 clauses.len()
             );
         }
-        SudokuCommand::Solve { puzzle } => {
-            let Some(grid) = get_sudoku_grid(&puzzle) else {
-                println!("Can't find {puzzle} - try easy, harder or hard");
-                return Ok(());
-            };
+        SudokuCommand::Solve {
+            puzzle,
+            from_file,
+            box_size,
+        } => {
+            let grid = resolve_sudoku_grid(&puzzle, &from_file, box_size)?;
 
             println!("Attempting to solve puzzle...");
             println!("{grid}");
 
-            match sudoku::solve_sudoku(&grid) {
+            match sudoku::solve_sudoku_with(&grid, engine) {
                 Ok(Some(solution)) => {
                     println!("Solution found:");
                     println!("{solution}");
@@ -206,7 +305,7 @@ fn handle_sudoku(command: SudokuCommand) -> Result<()> {
             }
 
             println!("\nChecking how many solutions this puzzle has...");
-            match sudoku::find_all_solutions(&grid) {
+            match sudoku::find_all_solutions_with(&grid, engine) {
                 Ok(solutions) => {
                     println!("Found {} solution(s).", solutions.len());
                     // A well-formed puzzle should have exactly 1.
@@ -214,19 +313,48 @@ fn handle_sudoku(command: SudokuCommand) -> Result<()> {
                 Err(e) => println!("An error occurred: {e}"),
             }
         }
+        SudokuCommand::GeneratePuzzle {
+            box_size,
+            difficulty,
+        } => {
+            println!("Generating a {difficulty:?} puzzle with box size {box_size}...");
+            let grid = sudoku::generate_puzzle(box_size, difficulty);
+            println!("{grid}");
+
+            let clues = sudoku::clue_count(&grid);
+            let solutions = sudoku::find_all_solutions_with(&grid, engine)?.len();
+            println!("{clues} clues, {solutions} solution(s)");
+        }
     }
 
     Ok(())
 }
 
-fn handle_map_colour(command: MapColourCommand) -> Result<()> {
+fn resolve_map(map: &str) -> Result<map_colour::Map> {
+    map_colour::get_map(map)
+        .ok_or_else(|| anyhow::anyhow!("'{map}' is not a known map - try australia or usa"))
+}
+
+fn handle_map_colour(command: MapColourCommand, engine: sat_puzzles::Engine) -> Result<()> {
     match command {
         MapColourCommand::List => println!("australia, usa"),
         MapColourCommand::Generate { map, colours } => {
-            println!("Generate CNF for {map} with {colours} colours");
+            let map_data = resolve_map(&map)?;
+            println!("Generating CNF for {map} with {colours} colours...");
+            let clauses = map_colour::generate_clauses(&map_data, colours);
+            let output = "map_colour.cnf";
+            sat_puzzles::write_clauses(output, &clauses)?;
         }
         MapColourCommand::Solve { map, colours } => {
-            println!("Solving {map} with {colours} colours");
+            let map_data = resolve_map(&map)?;
+            println!("Solving {map} with {colours} colours...");
+            match map_colour::solve_map_colouring_with(&map_data, colours, engine)? {
+                Some(colouring) => {
+                    println!("Solution found:");
+                    print!("{colouring}");
+                }
+                None => println!("No valid coloring with {colours} colours"),
+            }
         }
     }
     Ok(())
@@ -234,10 +362,12 @@ fn handle_map_colour(command: MapColourCommand) -> Result<()> {
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let engine = cli.engine;
 
     match cli.puzzle {
-        PuzzleType::NQueens { command } => handle_nqueens(command),
-        PuzzleType::Sudoku { command } => handle_sudoku(command),
-        PuzzleType::MapColour { command } => handle_map_colour(command),
+        PuzzleType::NQueens { command } => handle_nqueens(command, engine),
+        PuzzleType::Sudoku { command } => handle_sudoku(command, engine),
+        PuzzleType::MapColour { command } => handle_map_colour(command, engine),
+        PuzzleType::Cnf { command } => handle_cnf(command),
     }
 }
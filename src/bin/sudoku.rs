@@ -1,6 +1,7 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use sat_puzzles::sudoku_sat::{SudokuGrid, decode_solution, generate_clauses};
+use sat_puzzles::SatEngine;
+use sat_puzzles::sudoku_sat::{SudokuGrid, decode_solution, generate_clauses, standard_constraints};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -31,6 +32,11 @@ enum Commands {
         /// Find all possible solutions instead of just one
         #[arg(short, long)]
         all: bool,
+
+        /// SAT backend to solve with (e.g. "varisat", or "splr" with the splr-backend
+        /// feature enabled), so solver engines can be benchmarked against each other.
+        #[arg(short, long, default_value = "varisat")]
+        engine: SatEngine,
     },
 }
 
@@ -46,26 +52,31 @@ fn main() -> Result<()> {
                 puzzle_file.display()
             );
             let grid = SudokuGrid::from_file(puzzle_file)?;
-            let clauses = generate_clauses(&grid);
+            let clauses = generate_clauses(&grid, &standard_constraints());
             sat_puzzles::write_clauses(output, &clauses)?;
             println!("CNF written to {}", output.display());
         }
-        Commands::Solve { puzzle_file, all } => {
+        Commands::Solve {
+            puzzle_file,
+            all,
+            engine,
+        } => {
             println!("Solving sudoku from {puzzle_file:?}");
             let grid = SudokuGrid::from_file(puzzle_file)?;
             println!("{grid}");
-            let clauses = generate_clauses(&grid);
+            let clauses = generate_clauses(&grid, &standard_constraints());
 
-            let raw_solutions_iterator = sat_puzzles::find_all_solutions(&clauses)?;
+            let raw_solutions_iterator = sat_puzzles::find_all_solutions_with(&clauses, *engine)?;
+            let box_size = grid.box_size();
 
             let solutions: Vec<SudokuGrid> = if *all {
                 raw_solutions_iterator
-                    .map(|model| decode_solution(&model))
+                    .map(|model| decode_solution(&model, box_size))
                     .collect()
             } else {
                 raw_solutions_iterator
                     .take(1)
-                    .map(|model| decode_solution(&model))
+                    .map(|model| decode_solution(&model, box_size))
                     .collect()
             };
 
@@ -0,0 +1,161 @@
+/// Produces puzzle instances that are guaranteed to have exactly one solution.
+///
+/// The approach is the same for every puzzle family: start from a full valid solution,
+/// then repeatedly blank out one more clue as long as the puzzle (re-encoded from scratch)
+/// still has a unique solution. `SolutionIterator` already adds a blocking clause after the
+/// first model it returns, so checking "more than one solution" only costs a second `solve()`
+/// call via `.take(2)`.
+use crate::find_all_solutions;
+
+/// Coarse difficulty knob: how many clues a generated puzzle should retain, expressed as a
+/// fraction of the total number of cells.
+#[derive(Debug, Clone, Copy)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn min_clues(self, total_cells: usize) -> usize {
+        let percent = match self {
+            Difficulty::Easy => 40,
+            Difficulty::Medium => 30,
+            Difficulty::Hard => 22,
+        };
+        (total_cells * percent / 100).max(1)
+    }
+}
+
+impl std::str::FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            other => Err(format!("unknown difficulty '{other}'")),
+        }
+    }
+}
+
+/// Returns true iff `clauses` has exactly one satisfying model.
+pub fn has_unique_solution(clauses: &[Vec<isize>]) -> bool {
+    match find_all_solutions(clauses) {
+        Ok(iter) => iter.take(2).count() == 1,
+        Err(_) => false,
+    }
+}
+
+pub mod sudoku {
+    use super::{Difficulty, has_unique_solution};
+    use crate::sudoku_sat::{SudokuGrid, decode_solution, generate_clauses, standard_constraints};
+    use rand::seq::SliceRandom;
+
+    /// Solves an empty `box_size^2 x box_size^2` grid and returns the first full valid
+    /// solution found.
+    pub fn full_solution(box_size: usize) -> SudokuGrid {
+        let empty = SudokuGrid::empty(box_size);
+        let clauses = generate_clauses(&empty, &standard_constraints());
+        let model = crate::find_all_solutions(&clauses)
+            .unwrap()
+            .next()
+            .expect("an empty Sudoku grid always has a solution");
+        decode_solution(&model, box_size)
+    }
+
+    /// Generates a Sudoku puzzle with exactly one solution, retaining as few clues as the
+    /// given `difficulty` allows without losing uniqueness.
+    pub fn generate(box_size: usize, difficulty: Difficulty) -> SudokuGrid {
+        let mut grid = full_solution(box_size);
+        let n = box_size * box_size;
+
+        let mut cells: Vec<(usize, usize)> =
+            (0..n).flat_map(|r| (0..n).map(move |c| (r, c))).collect();
+        cells.shuffle(&mut rand::rng());
+
+        let min_clues = difficulty.min_clues(n * n);
+        let mut num_clues = n * n;
+
+        for (r, c) in cells {
+            if num_clues <= min_clues {
+                break;
+            }
+
+            let removed = grid.get(r, c);
+            grid.set(r, c, 0);
+
+            let clauses = generate_clauses(&grid, &standard_constraints());
+            if has_unique_solution(&clauses) {
+                num_clues -= 1;
+            } else {
+                grid.set(r, c, removed);
+            }
+        }
+
+        grid
+    }
+}
+
+pub mod numbrix {
+    use super::{Difficulty, has_unique_solution};
+    use crate::numbrix_sat::{Adjacency, NumbrixGrid, decode_solution, generate_clauses};
+    use rand::seq::SliceRandom;
+
+    /// Solves an empty `n x n` grid and returns the first full valid path found.
+    pub fn full_solution(n: usize) -> NumbrixGrid {
+        let empty = NumbrixGrid::empty(n);
+        let clauses = generate_clauses(&empty, Adjacency::VonNeumann);
+        let model = crate::find_all_solutions(&clauses)
+            .unwrap()
+            .next()
+            .expect("an empty Numbrix grid always has a solution");
+        decode_solution(&model, n).into()
+    }
+
+    /// Generates a Numbrix puzzle with exactly one solution, retaining as few clues as the
+    /// given `difficulty` allows without losing uniqueness.
+    pub fn generate(n: usize, difficulty: Difficulty) -> NumbrixGrid {
+        let mut grid = full_solution(n);
+
+        let mut cells: Vec<(usize, usize)> =
+            (0..n).flat_map(|r| (0..n).map(move |c| (r, c))).collect();
+        cells.shuffle(&mut rand::rng());
+
+        let min_clues = difficulty.min_clues(n * n);
+        let mut num_clues = n * n;
+
+        for (r, c) in cells {
+            if num_clues <= min_clues {
+                break;
+            }
+
+            let removed = grid.get(r, c);
+            grid.set(r, c, 0);
+
+            let clauses = generate_clauses(&grid, Adjacency::VonNeumann);
+            if has_unique_solution(&clauses) {
+                num_clues -= 1;
+            } else {
+                grid.set(r, c, removed);
+            }
+        }
+
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::numbrix::generate;
+    use super::Difficulty;
+    use crate::numbrix_sat::{Adjacency, generate_clauses};
+
+    #[test]
+    fn test_numbrix_generate_has_unique_solution() {
+        let grid = generate(3, Difficulty::Easy);
+        let clauses = generate_clauses(&grid, Adjacency::VonNeumann);
+        assert_eq!(crate::find_all_solutions(&clauses).unwrap().count(), 1);
+    }
+}
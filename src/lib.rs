@@ -2,9 +2,15 @@
 ///
 /// This crate provides tools to encode puzzles into Conjunctive Normal Form (CNF)
 /// and find solutions using a SAT solver.
+pub mod generator;
+pub mod map_colour;
 pub mod mapcolouring_sat;
 pub mod minesweeper_sat;
+pub mod native_sat;
+pub mod nonogram_sat;
 pub mod nqueens_sat;
+pub mod numbrix_sat;
+pub mod sudoku;
 pub mod sudoku_sat;
 
 use anyhow::Result;
@@ -23,6 +29,32 @@ fn num_vars(clauses: &[Vec<isize>]) -> usize {
         .unwrap_or(0) // Handle case with no clauses
 }
 
+/// Reads a standard DIMACS CNF file back into clauses, the inverse of `write_clauses`.
+///
+/// `c` comment lines and the `p cnf <vars> <clauses>` header are skipped; every other line's
+/// whitespace-separated integers are accumulated into the current clause until a `0`
+/// terminates it, so a clause may be split across several lines.
+pub fn read_clauses<P: AsRef<Path>>(input: P) -> Result<Vec<Vec<isize>>> {
+    let content = std::fs::read_to_string(input)?;
+    let mut clauses = Vec::new();
+    let mut current = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('c') || line.starts_with('p') {
+            continue;
+        }
+        for token in line.split_whitespace() {
+            let literal: isize = token.parse()?;
+            if literal == 0 {
+                clauses.push(std::mem::take(&mut current));
+            } else {
+                current.push(literal);
+            }
+        }
+    }
+    Ok(clauses)
+}
+
 pub fn write_clauses<P: AsRef<Path>>(output: P, clauses: &[Vec<isize>]) -> Result<()> {
     let num_vars = num_vars(clauses); //n * n;
 
@@ -49,38 +81,387 @@ pub fn write_clauses<P: AsRef<Path>>(output: P, clauses: &[Vec<isize>]) -> Resul
     Ok(())
 }
 
-// holds the state needed to keep finding the next solution.
-pub struct SolutionIterator<'a> {
-    solver: Solver<'a>,
+/// Abstracts over the SAT solver used to satisfy a clause set, so `find_all_solutions` and
+/// the incremental/assumption queries built on it don't hard-depend on a single crate's API.
+/// Clauses and models are exchanged as plain DIMACS-style signed integers — the
+/// representation every puzzle module already speaks — rather than exposing any one
+/// backend's native literal type.
+pub trait SatBackend {
+    /// Adds a single clause (DIMACS-style signed integers) to the backend's formula.
+    fn add_clause(&mut self, clause: &[isize]);
+
+    /// Solves under the given assumptions (DIMACS-style signed integers), returning the
+    /// satisfying model if one exists.
+    fn solve(&mut self, assumptions: &[isize]) -> Option<Vec<isize>>;
 }
 
-impl<'a> Iterator for SolutionIterator<'a> {
-    type Item = Vec<Lit>;
+/// The default backend, built on the `varisat` crate.
+struct VarisatBackend(Solver<'static>);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.solver.solve().unwrap_or(false) {
-            let model = self.solver.model().expect("No model found");
-            // block the exact same solution from being found again
-            // !(l1 AND l2 ... and lN) = (!l1 OR !l2 OR ... OR !lN)
-            let blocking_clause: Vec<Lit> = model.iter().map(|&lit| !lit).collect();
-            self.solver.add_clause(&blocking_clause);
-            Some(model)
-        } else {
-            None
-        }
+impl VarisatBackend {
+    fn new() -> Self {
+        VarisatBackend(Solver::new())
     }
 }
 
-/// Finds all solutions and returns them as a memory-efficient iterator.
-pub fn find_all_solutions(clauses: &[Vec<isize>]) -> Result<SolutionIterator> {
-    let mut solver = Solver::new();
-    for clause in clauses {
-        solver.add_clause(
+impl SatBackend for VarisatBackend {
+    fn add_clause(&mut self, clause: &[isize]) {
+        self.0.add_clause(
             &clause
                 .iter()
                 .map(|&lit| Lit::from_dimacs(lit))
                 .collect::<Vec<_>>(),
         );
     }
-    Ok(SolutionIterator { solver })
+
+    fn solve(&mut self, assumptions: &[isize]) -> Option<Vec<isize>> {
+        let assumptions: Vec<Lit> = assumptions.iter().map(|&lit| Lit::from_dimacs(lit)).collect();
+        self.0.assume(&assumptions);
+        if self.0.solve().unwrap_or(false) {
+            Some(
+                self.0
+                    .model()
+                    .expect("solve() returned true, so a model must exist")
+                    .iter()
+                    .map(|lit| lit.to_dimacs())
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+/// An alternative backend built on `splr`, a modern CDCL solver, so solver engines can be
+/// benchmarked against each other on the same CNF. Enabled by the `splr-backend` Cargo
+/// feature.
+///
+/// splr's public API solves a whole CNF in one shot rather than exposing an incremental
+/// assumption interface, so each `solve()` call here re-solves the clauses accumulated so
+/// far with the assumptions folded in as extra unit clauses.
+#[cfg(feature = "splr-backend")]
+struct SplrBackend {
+    clauses: Vec<Vec<isize>>,
+}
+
+#[cfg(feature = "splr-backend")]
+impl SplrBackend {
+    fn new() -> Self {
+        SplrBackend {
+            clauses: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "splr-backend")]
+impl SatBackend for SplrBackend {
+    fn add_clause(&mut self, clause: &[isize]) {
+        self.clauses.push(clause.to_vec());
+    }
+
+    fn solve(&mut self, assumptions: &[isize]) -> Option<Vec<isize>> {
+        let mut cnf: Vec<Vec<i32>> = self
+            .clauses
+            .iter()
+            .map(|clause| clause.iter().map(|&lit| lit as i32).collect())
+            .collect();
+        cnf.extend(assumptions.iter().map(|&lit| vec![lit as i32]));
+
+        match splr::Solver::try_from(cnf).ok()?.solve() {
+            Ok(splr::Certificate::SAT(model)) => {
+                Some(model.into_iter().map(|lit| lit as isize).collect())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Selects which `SatBackend` implementation to solve with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SatEngine {
+    #[default]
+    Varisat,
+    #[cfg(feature = "splr-backend")]
+    Splr,
+}
+
+impl SatEngine {
+    fn build(self) -> Box<dyn SatBackend> {
+        match self {
+            SatEngine::Varisat => Box::new(VarisatBackend::new()),
+            #[cfg(feature = "splr-backend")]
+            SatEngine::Splr => Box::new(SplrBackend::new()),
+        }
+    }
+}
+
+impl std::str::FromStr for SatEngine {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "varisat" => Ok(SatEngine::Varisat),
+            #[cfg(feature = "splr-backend")]
+            "splr" => Ok(SatEngine::Splr),
+            other => Err(format!("unknown SAT engine '{other}'")),
+        }
+    }
+}
+
+// Holds the state needed to keep finding the next solution. Also doubles as the crate's
+// incremental/assumption solver: the same persistent `SatBackend` can be re-queried under
+// different assumed literals without losing the clauses it has already learned, and without
+// rebuilding the formula from scratch.
+pub struct SolutionIterator {
+    backend: Box<dyn SatBackend>,
+    assumptions: Vec<isize>,
+}
+
+impl SolutionIterator {
+    fn from_backend(backend: Box<dyn SatBackend>) -> Self {
+        SolutionIterator {
+            backend,
+            assumptions: Vec::new(),
+        }
+    }
+
+    /// Fixes a set of literals (DIMACS ints) to assume on every subsequent solve, replacing
+    /// any previously assumed literals. Clauses already learned by the backend are kept.
+    pub fn assume(&mut self, assumptions: &[isize]) {
+        self.assumptions = assumptions.to_vec();
+    }
+
+    /// Solves once under the current assumptions, without blocking the model found (so it
+    /// may be returned again by a later call). Returns `None` if unsatisfiable.
+    pub fn solve_once(&mut self) -> Option<Vec<Lit>> {
+        self.backend
+            .solve(&self.assumptions)
+            .map(|model| model.into_iter().map(Lit::from_dimacs).collect())
+    }
+}
+
+impl Iterator for SolutionIterator {
+    type Item = Vec<Lit>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let model = self.backend.solve(&self.assumptions)?;
+        // block the exact same solution from being found again
+        // !(l1 AND l2 ... and lN) = (!l1 OR !l2 OR ... OR !lN)
+        let blocking_clause: Vec<isize> = model.iter().map(|&lit| -lit).collect();
+        self.backend.add_clause(&blocking_clause);
+        Some(model.into_iter().map(Lit::from_dimacs).collect())
+    }
+}
+
+/// Finds all solutions and returns them as a memory-efficient iterator, using the default
+/// (`varisat`) backend.
+///
+/// This builds the same persistent backend that backs assumption-based queries (see
+/// `SolutionIterator::assume`/`solve_once`); enumerating with no assumptions set is just the
+/// special case of the incremental solver used here.
+pub fn find_all_solutions(clauses: &[Vec<isize>]) -> Result<SolutionIterator> {
+    find_all_solutions_with(clauses, SatEngine::default())
+}
+
+/// Same as `find_all_solutions`, but lets the caller pick which `SatBackend` to solve with —
+/// e.g. to benchmark `SatEngine::Varisat` against `SatEngine::Splr` on the same CNF.
+pub fn find_all_solutions_with(
+    clauses: &[Vec<isize>],
+    engine: SatEngine,
+) -> Result<SolutionIterator> {
+    let mut backend = engine.build();
+    for clause in clauses {
+        backend.add_clause(clause);
+    }
+    Ok(SolutionIterator::from_backend(backend))
+}
+
+/// Selects which solving *strategy* to enumerate solutions with — not to be confused with
+/// `SatEngine`, which picks a `SatBackend` implementation within the SAT strategy. `Native`
+/// sidesteps `varisat`/`splr` entirely in favour of `native_sat`'s dependency-free backtracking
+/// search, at the cost of not scaling to the larger encodings (e.g. a full 9x9 Sudoku).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Engine {
+    #[default]
+    Sat,
+    Native,
+}
+
+impl std::str::FromStr for Engine {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "sat" => Ok(Engine::Sat),
+            "native" => Ok(Engine::Native),
+            other => Err(format!("unknown engine '{other}' (expected 'sat' or 'native')")),
+        }
+    }
+}
+
+/// Above this many variables, `native_sat`'s plain chronological backtracking (no unit
+/// propagation, no variable-ordering heuristic) stops being practical — confirmed empirically: a
+/// 4x4 Sudoku (64 variables) solves instantly, a 9x9 one (729 variables) never returns.
+const NATIVE_ENGINE_MAX_VARS: usize = 200;
+
+/// Enumerates all solutions to `clauses` with the chosen `Engine`, boxing the two strategies'
+/// distinct iterator types behind a common interface so callers can swap between them freely.
+/// Rejects `Engine::Native` outright once `clauses` is too large for it to solve in reasonable
+/// time — see `NATIVE_ENGINE_MAX_VARS`.
+pub fn find_all_solutions_via(
+    clauses: &[Vec<isize>],
+    engine: Engine,
+) -> Result<Box<dyn Iterator<Item = Vec<Lit>>>> {
+    match engine {
+        Engine::Sat => Ok(Box::new(find_all_solutions(clauses)?)),
+        Engine::Native => {
+            let vars = num_vars(clauses);
+            if vars > NATIVE_ENGINE_MAX_VARS {
+                anyhow::bail!(
+                    "the native engine's plain backtracking search won't scale to a \
+                     {vars}-variable encoding (limit {NATIVE_ENGINE_MAX_VARS}); use the \
+                     default `sat` engine instead"
+                );
+            }
+            Ok(Box::new(native_sat::find_all_solutions(clauses)))
+        }
+    }
+}
+
+/// Checks whether `clauses` is satisfiable with the given literals (DIMACS ints) held fixed,
+/// without rebuilding the formula — e.g. "does this Sudoku cell admit value v?" by assuming
+/// that cell's variable and checking SAT.
+pub fn is_consistent_with(clauses: &[Vec<isize>], assumptions: &[isize]) -> Result<bool> {
+    let mut iter = find_all_solutions(clauses)?;
+    iter.assume(assumptions);
+    Ok(iter.solve_once().is_some())
+}
+
+/// Encodes "at most one of `vars` is true" with the naive pairwise encoding: a clause
+/// `(-x_i OR -x_j)` for every pair. O(k^2) clauses, but no auxiliary variables, which makes
+/// it cheaper than the sequential encoding for small groups.
+pub fn at_most_one_pairwise(vars: &[isize], clauses: &mut Vec<Vec<isize>>) {
+    for i in 0..vars.len() {
+        for j in (i + 1)..vars.len() {
+            clauses.push(vec![-vars[i], -vars[j]]);
+        }
+    }
+}
+
+/// Encodes "at most one of `vars` is true" using Sinz's sequential-counter encoding.
+///
+/// Introduces `k - 1` auxiliary "register" variables `s_1..s_{k-1}` (`s_i` meaning "some
+/// `x_1..x_i` is true") allocated from `*next_var`, which is advanced past them. This costs
+/// O(k) clauses and auxiliaries instead of the pairwise encoding's O(k^2) clauses.
+///
+/// Ref: Carsten Sinz, "Towards an Optimal CNF Encoding of Boolean Cardinality Constraints", 2005
+pub fn at_most_one_sequential(
+    vars: &[isize],
+    next_var: &mut usize,
+    clauses: &mut Vec<Vec<isize>>,
+) {
+    let k = vars.len();
+    if k < 2 {
+        return;
+    }
+
+    let s: Vec<isize> = (0..k - 1)
+        .map(|_| {
+            let v = *next_var as isize;
+            *next_var += 1;
+            v
+        })
+        .collect();
+
+    // ¬x_1 ∨ s_1
+    clauses.push(vec![-vars[0], s[0]]);
+
+    for i in 1..k - 1 {
+        clauses.push(vec![-s[i - 1], s[i]]); // ¬s_{i-1} ∨ s_i
+        clauses.push(vec![-vars[i], s[i]]); // ¬x_i ∨ s_i
+        clauses.push(vec![-vars[i], -s[i - 1]]); // ¬x_i ∨ ¬s_{i-1}
+    }
+
+    // ¬x_k ∨ ¬s_{k-1}
+    clauses.push(vec![-vars[k - 1], -s[k - 2]]);
+}
+
+/// Groups used by the commander encoding (see `at_most_one_commander`). Small enough that the
+/// pairwise encoding inside each group stays cheap.
+const COMMANDER_GROUP_SIZE: usize = 3;
+
+/// Encodes "at most one of `vars` is true" using the commander encoding (Klieber & Kwon,
+/// 2007): partitions `vars` into groups of `COMMANDER_GROUP_SIZE`, pairwise-encodes "at most
+/// one" within each group, and introduces one auxiliary "commander" variable per group that is
+/// true iff some variable in its group is — then recurses on the list of commanders. This
+/// keeps auxiliary/clause growth close to linear, like the sequential encoding, while keeping
+/// every individual constraint small (a property the sequential encoding's single long chain
+/// doesn't have).
+pub fn at_most_one_commander(vars: &[isize], next_var: &mut usize, clauses: &mut Vec<Vec<isize>>) {
+    if vars.len() <= COMMANDER_GROUP_SIZE {
+        at_most_one_pairwise(vars, clauses);
+        return;
+    }
+
+    let commanders: Vec<isize> = vars
+        .chunks(COMMANDER_GROUP_SIZE)
+        .map(|group| {
+            let commander = *next_var as isize;
+            *next_var += 1;
+
+            at_most_one_pairwise(group, clauses);
+            // ¬commander ∨ x_1 ∨ ... ∨ x_k: commander true implies some group member is true.
+            let mut at_least_one = vec![-commander];
+            at_least_one.extend_from_slice(group);
+            clauses.push(at_least_one);
+            // ¬x_i ∨ commander: any group member true implies the commander is true.
+            for &v in group {
+                clauses.push(vec![-v, commander]);
+            }
+
+            commander
+        })
+        .collect();
+
+    at_most_one_commander(&commanders, next_var, clauses);
+}
+
+/// Encodes "at most one of `vars` is true", choosing the pairwise encoding for small groups
+/// (where it is cheaper) and falling back to Sinz's sequential encoding once the quadratic
+/// blow-up of the pairwise encoding would dominate clause count.
+pub fn at_most_one(vars: &[isize], next_var: &mut usize, clauses: &mut Vec<Vec<isize>>) {
+    const PAIRWISE_THRESHOLD: usize = 6;
+    if vars.len() <= PAIRWISE_THRESHOLD {
+        at_most_one_pairwise(vars, clauses);
+    } else {
+        at_most_one_sequential(vars, next_var, clauses);
+    }
+}
+
+/// Selects which "at most one" encoding strategy `generate_clauses`-style puzzle encoders
+/// should use for their cardinality groups, so callers can pick deliberately instead of always
+/// getting `at_most_one`'s automatic pairwise/sequential choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AtMostOne {
+    /// Naive pairwise encoding: exact, no auxiliaries, but O(k^2) clauses.
+    Pairwise,
+    /// Sinz's sequential-counter encoding: O(k) clauses and auxiliaries.
+    Sequential,
+    /// The commander encoding: near-linear clause/auxiliary growth via grouped commanders.
+    Commander,
+    /// Pairwise for small groups, sequential once pairwise's quadratic blow-up would dominate.
+    #[default]
+    Auto,
+}
+
+impl AtMostOne {
+    /// Emits this strategy's "at most one of `vars` is true" clauses.
+    pub fn encode(self, vars: &[isize], next_var: &mut usize, clauses: &mut Vec<Vec<isize>>) {
+        match self {
+            AtMostOne::Pairwise => at_most_one_pairwise(vars, clauses),
+            AtMostOne::Sequential => at_most_one_sequential(vars, next_var, clauses),
+            AtMostOne::Commander => at_most_one_commander(vars, next_var, clauses),
+            AtMostOne::Auto => at_most_one(vars, next_var, clauses),
+        }
+    }
 }
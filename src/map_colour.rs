@@ -0,0 +1,226 @@
+//! A small, self-contained map-colouring solver for the `satpuzzles` CLI's `mapcolor`
+//! subcommand.
+//!
+//! `mapcolouring_sat` parses an arbitrary adjacency file into its own `Map`; this module only
+//! ever needs one of a couple of bundled maps (`australia`, `usa`) selected by name, so it keeps
+//! its own minimal `Map` and clause generation rather than threading a file-parsing API through
+//! a case where there's no file to parse.
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fmt;
+use varisat::Lit;
+
+/// A map's regions and their (symmetric) adjacency lists.
+pub struct Map {
+    regions: Vec<String>,
+    adjacencies: HashMap<String, Vec<String>>,
+}
+
+/// The classic Australian map-colouring problem: six states and the Northern Territory, plus
+/// the island state of Tasmania, which borders nothing.
+pub fn australia() -> Map {
+    build_map(&[
+        ("WA", &["NT", "SA"]),
+        ("NT", &["WA", "SA", "Q"]),
+        ("SA", &["WA", "NT", "Q", "NSW", "V"]),
+        ("Q", &["NT", "SA", "NSW"]),
+        ("NSW", &["SA", "Q", "V"]),
+        ("V", &["SA", "NSW"]),
+        ("T", &[]),
+    ])
+}
+
+/// The 48 contiguous US states, adjacent if they share a border.
+pub fn usa() -> Map {
+    build_map(&[
+        ("WA", &["OR", "ID"]),
+        ("OR", &["WA", "ID", "NV", "CA"]),
+        ("CA", &["OR", "NV", "AZ"]),
+        ("NV", &["OR", "CA", "ID", "UT", "AZ"]),
+        ("ID", &["WA", "OR", "NV", "MT", "WY", "UT"]),
+        ("UT", &["NV", "ID", "WY", "CO", "AZ"]),
+        ("AZ", &["CA", "NV", "UT", "NM"]),
+        ("MT", &["ID", "ND", "SD", "WY"]),
+        ("WY", &["ID", "UT", "MT", "SD", "NE", "CO"]),
+        ("CO", &["UT", "WY", "NE", "KS", "OK", "NM"]),
+        ("NM", &["AZ", "CO", "OK", "TX"]),
+        ("ND", &["MT", "SD", "MN"]),
+        ("SD", &["MT", "WY", "ND", "NE", "IA", "MN"]),
+        ("NE", &["WY", "CO", "SD", "IA", "MO", "KS"]),
+        ("KS", &["CO", "NE", "MO", "OK"]),
+        ("OK", &["NM", "CO", "KS", "MO", "AR", "TX"]),
+        ("TX", &["NM", "OK", "AR", "LA"]),
+        ("MN", &["ND", "SD", "IA", "WI"]),
+        ("IA", &["SD", "NE", "MN", "MO", "WI", "IL"]),
+        ("MO", &["NE", "KS", "OK", "IA", "AR", "IL", "KY", "TN"]),
+        ("AR", &["OK", "TX", "MO", "LA", "MS", "TN"]),
+        ("LA", &["TX", "AR", "MS"]),
+        ("WI", &["MN", "IA", "IL", "MI"]),
+        ("IL", &["IA", "MO", "WI", "IN", "KY"]),
+        ("MS", &["AR", "LA", "TN", "AL"]),
+        ("MI", &["WI", "IN", "OH"]),
+        ("IN", &["IL", "MI", "OH", "KY"]),
+        ("KY", &["MO", "IL", "IN", "OH", "WV", "VA", "TN"]),
+        ("TN", &["MO", "AR", "MS", "KY", "VA", "NC", "GA", "AL"]),
+        ("AL", &["MS", "TN", "GA", "FL"]),
+        ("OH", &["MI", "IN", "KY", "WV", "PA"]),
+        ("WV", &["KY", "OH", "PA", "MD", "VA"]),
+        ("VA", &["KY", "TN", "WV", "MD", "DC", "NC"]),
+        ("NC", &["TN", "VA", "SC", "GA"]),
+        ("GA", &["TN", "AL", "NC", "SC", "FL"]),
+        ("SC", &["NC", "GA"]),
+        ("FL", &["AL", "GA"]),
+        ("PA", &["OH", "WV", "NY", "NJ", "DE", "MD"]),
+        ("MD", &["WV", "VA", "PA", "DE", "DC"]),
+        ("DC", &["VA", "MD"]),
+        ("DE", &["PA", "MD", "NJ"]),
+        ("NJ", &["PA", "DE", "NY"]),
+        ("NY", &["PA", "NJ", "CT", "MA", "VT"]),
+        ("CT", &["NY", "MA", "RI"]),
+        ("RI", &["CT", "MA"]),
+        ("MA", &["NY", "CT", "RI", "NH", "VT"]),
+        ("VT", &["NY", "MA", "NH"]),
+        ("NH", &["MA", "VT", "ME"]),
+        ("ME", &["NH"]),
+    ])
+}
+
+/// Builds a `Map` from a list of `(region, neighbors)` pairs, symmetrizing the adjacency so
+/// each region's list includes every neighbor that names it, even if the reverse edge wasn't
+/// listed explicitly.
+fn build_map(edges: &[(&str, &[&str])]) -> Map {
+    let regions: Vec<String> = edges.iter().map(|&(r, _)| r.to_string()).collect();
+    let mut adjacencies: HashMap<String, Vec<String>> =
+        regions.iter().map(|r| (r.clone(), Vec::new())).collect();
+    for &(region, neighbors) in edges {
+        for &neighbor in neighbors {
+            adjacencies.get_mut(region).unwrap().push(neighbor.to_string());
+            let back = adjacencies.get_mut(neighbor).unwrap();
+            if !back.contains(&region.to_string()) {
+                back.push(region.to_string());
+            }
+        }
+    }
+    Map {
+        regions,
+        adjacencies,
+    }
+}
+
+/// Looks up a bundled map by name (`"australia"` or `"usa"`).
+pub fn get_map(name: &str) -> Option<Map> {
+    match name {
+        "australia" => Some(australia()),
+        "usa" => Some(usa()),
+        _ => None,
+    }
+}
+
+/// A region -> colour-name assignment.
+pub struct Colouring(HashMap<String, String>);
+
+impl fmt::Display for Colouring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut sorted: Vec<_> = self.0.iter().collect();
+        sorted.sort_by_key(|(region, _)| *region);
+        for (region, colour) in sorted {
+            writeln!(f, "{region}: {colour}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The name of the `i`-th colour (`0`-based), `"colour 1"`, `"colour 2"`, etc.
+fn colour_name(i: usize) -> String {
+    format!("colour {}", i + 1)
+}
+
+fn var(colours: usize, region_idx: usize, colour_idx: usize) -> isize {
+    (region_idx * colours + colour_idx + 1) as isize
+}
+
+/// Generates CNF clauses for colouring `map` with `colours` colours: variables `x(region,
+/// colour)`, an at-least-one clause per region, pairwise at-most-one clauses per region, and
+/// for every adjacent pair `(a, b)` a clause `¬x(a,k) ∨ ¬x(b,k)` for each colour `k`.
+pub fn generate_clauses(map: &Map, colours: usize) -> Vec<Vec<isize>> {
+    let mut clauses = Vec::new();
+    let region_to_idx: HashMap<&str, usize> = map
+        .regions
+        .iter()
+        .enumerate()
+        .map(|(i, r)| (r.as_str(), i))
+        .collect();
+
+    // --- Each region gets at least one colour ---
+    for r_idx in 0..map.regions.len() {
+        clauses.push((0..colours).map(|c_idx| var(colours, r_idx, c_idx)).collect());
+    }
+
+    // --- Each region gets at most one colour ---
+    for r_idx in 0..map.regions.len() {
+        let vars: Vec<isize> = (0..colours).map(|c_idx| var(colours, r_idx, c_idx)).collect();
+        crate::at_most_one_pairwise(&vars, &mut clauses);
+    }
+
+    // --- Adjacent regions can't share a colour ---
+    for region in &map.regions {
+        let r_idx = region_to_idx[region.as_str()];
+        for neighbor in &map.adjacencies[region] {
+            let n_idx = region_to_idx[neighbor.as_str()];
+            if r_idx < n_idx {
+                for c_idx in 0..colours {
+                    clauses.push(vec![-var(colours, r_idx, c_idx), -var(colours, n_idx, c_idx)]);
+                }
+            }
+        }
+    }
+
+    clauses
+}
+
+fn decode_solution(model: &[Lit], map: &Map, colours: usize) -> Colouring {
+    let mut assignment = HashMap::new();
+    for &lit in model.iter().filter(|l| l.is_positive()) {
+        let index = lit.var().to_dimacs() as usize - 1;
+        let r_idx = index / colours;
+        let c_idx = index % colours;
+        if let Some(region) = map.regions.get(r_idx) {
+            assignment.insert(region.clone(), colour_name(c_idx));
+        }
+    }
+    Colouring(assignment)
+}
+
+/// Solves `map` with `colours` colours, returning the first assignment found (or `None` if no
+/// valid colouring exists), using the default (`Sat`) engine.
+pub fn solve_map_colouring(map: &Map, colours: usize) -> Result<Option<Colouring>> {
+    solve_map_colouring_with(map, colours, crate::Engine::default())
+}
+
+/// Same as `solve_map_colouring`, but lets the caller pick which solving engine to use.
+pub fn solve_map_colouring_with(
+    map: &Map,
+    colours: usize,
+    engine: crate::Engine,
+) -> Result<Option<Colouring>> {
+    let clauses = generate_clauses(map, colours);
+    Ok(crate::find_all_solutions_via(&clauses, engine)?
+        .next()
+        .map(|model| decode_solution(&model, map, colours)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_australia_needs_three_colours() {
+        assert!(solve_map_colouring(&australia(), 2).unwrap().is_none());
+        assert!(solve_map_colouring(&australia(), 3).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_usa_is_four_colourable() {
+        assert!(solve_map_colouring(&usa(), 4).unwrap().is_some());
+    }
+}
@@ -114,6 +114,18 @@ pub fn generate_clauses(
     states: &[String],
     colors: &[String],
     adjacencies: &HashMap<String, Vec<String>>,
+) -> Vec<Vec<isize>> {
+    generate_clauses_with(states, colors, adjacencies, crate::AtMostOne::default())
+}
+
+/// Same as `generate_clauses`, but lets the caller pick which `AtMostOne` cardinality encoding
+/// each state's "at most one color" group uses — useful for maps with many colors, where the
+/// default pairwise/sequential auto-choice may not be the cheapest option.
+pub fn generate_clauses_with(
+    states: &[String],
+    colors: &[String],
+    adjacencies: &HashMap<String, Vec<String>>,
+    strategy: crate::AtMostOne,
 ) -> Vec<Vec<isize>> {
     let mut clauses = Vec::new();
     let num_states = states.len();
@@ -140,13 +152,12 @@ pub fn generate_clauses(
     }
 
     // --- CONSTRAINT 2: Each state has at most one color ---
-    // For each state `s` and each pair of colors `c1, c2`: (-V_s,c1 OR -V_s,c2).
+    // Auxiliary variables for the sequential/commander encodings (if used) start past the
+    // puzzle's own 1..=num_states*num_colors variable range.
+    let mut next_var = num_states * num_colors + 1;
     for s_idx in 0..num_states {
-        for c1_idx in 0..num_colors {
-            for c2_idx in (c1_idx + 1)..num_colors {
-                clauses.push(vec![-var(s_idx, c1_idx), -var(s_idx, c2_idx)]);
-            }
-        }
+        let vars: Vec<isize> = (0..num_colors).map(|c_idx| var(s_idx, c_idx)).collect();
+        strategy.encode(&vars, &mut next_var, &mut clauses);
     }
 
     // --- CONSTRAINT 3: Adjacent states cannot have the same color ---
@@ -181,9 +192,11 @@ pub fn decode_solution(model: &[Lit], states: &[String], colors: &[String]) -> C
         (states[state_idx].as_str(), colors[color_idx].as_str())
     }
 
+    // Ignore sequential-encoding auxiliary variables, which live past num_states*num_colors.
+    let max_state_color_var = states.len() * colors.len();
     let solution_map = model
         .iter()
-        .filter(|lit| lit.is_positive())
+        .filter(|lit| lit.is_positive() && lit.var().to_dimacs() as usize <= max_state_color_var)
         .map(|lit| {
             let (state, color) = var_to_state_color(lit.var().to_dimacs() as usize, states, colors);
             // Convert to owned Strings for the final result
@@ -1,7 +1,9 @@
 use itertools::Itertools;
 use minesweeper_rs::Constraint;
-use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use varisat::Lit;
 
 /// Represents a single valid solution for the minefield layout.
@@ -256,12 +258,527 @@ pub fn decode_solution(
     }
 }
 
+/// Calculates "n choose k" using u128 to avoid overflow on large boards.
+pub fn combinations(n: u128, k: u128) -> u128 {
+    if k > n {
+        return 0;
+    }
+    if k == 0 || k == n {
+        return 1;
+    }
+    let k = if k > n - k { n - k } else { k };
+    (1..=k).fold(1, |acc, i| acc * (n - k + i) / i)
+}
+
+/// Decodes a model into the set of original board indices that are mines, using `var_map`
+/// (as returned by `generate_clauses`) to translate SAT variables back to cell indices.
+fn decode_mine_set(model: &[Lit], var_map: &HashMap<usize, isize>) -> HashSet<usize> {
+    let rev_var_map: HashMap<isize, usize> = var_map.iter().map(|(&k, &v)| (v, k)).collect();
+    model
+        .iter()
+        .filter(|lit| lit.is_positive())
+        .filter_map(|lit| rev_var_map.get(&lit.var().to_dimacs()).copied())
+        .collect()
+}
+
+/// Groups `local_constraints` into connected components: two unknown cells are adjacent iff
+/// they co-occur in some constraint. Returns, for each component, the indices into
+/// `local_constraints` that belong to it.
+fn connected_components(local_constraints: &[Constraint]) -> Vec<Vec<usize>> {
+    // Union-find over cell indices, keyed lazily since cell indices are sparse.
+    let mut parent: HashMap<usize, usize> = HashMap::new();
+
+    fn find(parent: &mut HashMap<usize, usize>, x: usize) -> usize {
+        let p = *parent.entry(x).or_insert(x);
+        if p == x {
+            x
+        } else {
+            let root = find(parent, p);
+            parent.insert(x, root);
+            root
+        }
+    }
+
+    for constraint in local_constraints {
+        if let Some(&first) = constraint.cells.first() {
+            for &cell in &constraint.cells[1..] {
+                let ra = find(&mut parent, first);
+                let rb = find(&mut parent, cell);
+                if ra != rb {
+                    parent.insert(ra, rb);
+                }
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (idx, constraint) in local_constraints.iter().enumerate() {
+        if let Some(&first) = constraint.cells.first() {
+            let root = find(&mut parent, first);
+            groups.entry(root).or_default().push(idx);
+        }
+    }
+
+    groups.into_values().collect()
+}
+
+/// The outcome of running [`propagate_trivial_deductions`] to a fixed point: cells that were
+/// forced to be safe or a mine by pure constraint counting, and the residual problem (with
+/// those cells and their influence on other constraints already removed) left for the SAT
+/// encoder to actually solve.
+pub struct Propagation {
+    pub safe: HashSet<usize>,
+    pub mine: HashSet<usize>,
+    pub residual_unknowns: Vec<usize>,
+    pub residual_constraints: Vec<Constraint>,
+}
+
+/// Applies the two trivial Minesweeper deduction rules directly on `local_constraints`,
+/// iterating to a fixed point before any clause is generated:
+/// - if a constraint's `count` is zero, every one of its still-unknown cells is safe;
+/// - if a constraint's `count` equals the number of its still-unknown cells, all of them are
+///   mines.
+///
+/// Deciding a cell removes it from the unknown set; deciding a mine also decrements the
+/// `count` of every other constraint that still references it. Either kind of change can
+/// make another constraint trivial in turn, so the whole pass repeats until nothing changes.
+pub fn propagate_trivial_deductions(
+    unknown_indices: &[usize],
+    local_constraints: &[Constraint],
+) -> Propagation {
+    let mut safe = HashSet::new();
+    let mut mine = HashSet::new();
+    let mut remaining: HashSet<usize> = unknown_indices.iter().copied().collect();
+    let mut constraints: Vec<Constraint> = local_constraints.to_vec();
+
+    loop {
+        let mut changed = false;
+
+        for constraint in &mut constraints {
+            let mines_removed = constraint
+                .cells
+                .iter()
+                .filter(|idx| mine.contains(*idx))
+                .count();
+            let before = constraint.cells.len();
+            constraint.cells.retain(|idx| remaining.contains(idx));
+            if constraint.cells.len() != before {
+                constraint.count -= mines_removed as f64;
+                changed = true;
+            }
+
+            if constraint.cells.is_empty() {
+                continue;
+            }
+
+            if constraint.count == 0.0 {
+                for &idx in &constraint.cells {
+                    safe.insert(idx);
+                    remaining.remove(&idx);
+                }
+                constraint.cells.clear();
+                changed = true;
+            } else if constraint.count as usize == constraint.cells.len() {
+                for &idx in &constraint.cells {
+                    mine.insert(idx);
+                    remaining.remove(&idx);
+                }
+                constraint.cells.clear();
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let residual_unknowns = unknown_indices
+        .iter()
+        .copied()
+        .filter(|idx| remaining.contains(idx))
+        .collect();
+    let residual_constraints = constraints
+        .into_iter()
+        .filter(|c| !c.cells.is_empty())
+        .collect();
+
+    Propagation {
+        safe,
+        mine,
+        residual_unknowns,
+        residual_constraints,
+    }
+}
+
+/// A connected border component's mine-count distribution: `counts[k]` is the number of
+/// solutions with exactly `k` mines in the component, and `tallies[k][i]` is how many of
+/// those solutions place a mine in `cells[i]` (original board index).
+pub struct ComponentDistribution {
+    pub cells: Vec<usize>,
+    pub counts: HashMap<usize, usize>,
+    pub tallies: HashMap<usize, Vec<usize>>,
+}
+
+/// Splits the border into independent components and solves each one on its own, instead of
+/// enumerating every combination of every constraint at once.
+///
+/// Runs [`propagate_trivial_deductions`] first and only feeds the residual constraints and
+/// cells to the SAT encoder. Cells it settles for free are represented as trivial one-cell
+/// components (a single guaranteed mine count, weight 1), so [`combine_components`] folds
+/// them into the overall convolution exactly like any other component, with no special case.
+/// Components that aren't settled for free are solved through `cache`, which recognizes
+/// components that are structurally identical (up to cell relabeling) to ones already seen.
+pub fn decompose_and_solve(
+    unknown_indices: &[usize],
+    local_constraints: &[Constraint],
+    cache: &mut ComponentCache,
+) -> Vec<ComponentDistribution> {
+    let propagation = propagate_trivial_deductions(unknown_indices, local_constraints);
+
+    let forced = propagation
+        .safe
+        .iter()
+        .map(|&idx| ComponentDistribution {
+            cells: vec![idx],
+            counts: HashMap::from([(0, 1)]),
+            tallies: HashMap::from([(0, vec![0])]),
+        })
+        .chain(propagation.mine.iter().map(|&idx| ComponentDistribution {
+            cells: vec![idx],
+            counts: HashMap::from([(1, 1)]),
+            tallies: HashMap::from([(1, vec![1])]),
+        }))
+        .collect::<Vec<_>>();
+
+    let solved: Vec<ComponentDistribution> = connected_components(&propagation.residual_constraints)
+        .into_iter()
+        .map(|constraint_indices| {
+            let constraints: Vec<Constraint> = constraint_indices
+                .iter()
+                .map(|&i| propagation.residual_constraints[i].clone())
+                .collect();
+
+            let mut cells: Vec<usize> = constraints
+                .iter()
+                .flat_map(|c| c.cells.iter().copied())
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect();
+            cells.sort_unstable();
+
+            cache.solve(cells, constraints)
+        })
+        .collect();
+
+    forced.into_iter().chain(solved).collect()
+}
+
+/// Computes a canonical structural signature for a connected component: a Zobrist-style hash
+/// — the XOR of independent per-cell and per-constraint contributions, so it doesn't depend
+/// on the order `cells`/`constraints` happen to be stored in — built from color-refinement
+/// classes, plus the permutation that sorts `cells` into canonical rank order by that same
+/// class. Two components that are isomorphic up to cell relabeling hash equal and produce
+/// canonical orders their per-cell tallies can be translated through interchangeably.
+fn canonical_signature(cells: &[usize], constraints: &[Constraint]) -> (u64, Vec<usize>) {
+    let cell_pos: HashMap<usize, usize> = cells.iter().enumerate().map(|(i, &c)| (c, i)).collect();
+
+    // 1-WL-style color refinement: each cell's color folds in the (count, sorted neighbor
+    // colors) of every constraint it belongs to, until colors stop changing.
+    let mut color: Vec<u64> = vec![1; cells.len()];
+    for _ in 0..=cells.len() {
+        let mut new_color = vec![0u64; cells.len()];
+        for (i, &cell) in cells.iter().enumerate() {
+            let mut hasher = DefaultHasher::new();
+            color[i].hash(&mut hasher);
+            for constraint in constraints {
+                if constraint.cells.contains(&cell) {
+                    let mut neighbor_colors: Vec<u64> = constraint
+                        .cells
+                        .iter()
+                        .filter_map(|c| cell_pos.get(c))
+                        .map(|&pos| color[pos])
+                        .collect();
+                    neighbor_colors.sort_unstable();
+                    constraint.count.to_bits().hash(&mut hasher);
+                    neighbor_colors.hash(&mut hasher);
+                }
+            }
+            new_color[i] = hasher.finish();
+        }
+        if new_color == color {
+            break;
+        }
+        color = new_color;
+    }
+
+    let mut canonical_order: Vec<usize> = (0..cells.len()).collect();
+    canonical_order.sort_by_key(|&i| color[i]);
+
+    let mut hash = 0u64;
+    for &c in &color {
+        let mut hasher = DefaultHasher::new();
+        ("cell", c).hash(&mut hasher);
+        hash ^= hasher.finish();
+    }
+    for constraint in constraints {
+        let mut neighbor_colors: Vec<u64> = constraint
+            .cells
+            .iter()
+            .filter_map(|c| cell_pos.get(c))
+            .map(|&pos| color[pos])
+            .collect();
+        neighbor_colors.sort_unstable();
+        let mut hasher = DefaultHasher::new();
+        ("constraint", constraint.count.to_bits(), &neighbor_colors).hash(&mut hasher);
+        hash ^= hasher.finish();
+    }
+
+    (hash, canonical_order)
+}
+
+/// Hit/miss counters for a [`ComponentCache`], so the speedup from reusing structurally
+/// identical components can be measured (e.g. across a `benchmark_solver` run of thousands of
+/// games).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+/// A solved component's distribution, keyed for reuse: counts don't depend on cell labeling,
+/// but tallies are stored in canonical rank order (see `canonical_signature`) so they can be
+/// translated back through any isomorphic component's own canonical order on a cache hit.
+struct CachedDistribution {
+    counts: HashMap<usize, usize>,
+    canonical_tallies: HashMap<usize, Vec<usize>>,
+}
+
+/// Caches component mine-count distributions keyed by canonical structural hash. Many border
+/// components recur with identical local structure — across different boards, and across
+/// successive moves of the same game — so solving one is often enough to answer for all of
+/// its structural twins.
+#[derive(Default)]
+pub struct ComponentCache {
+    entries: HashMap<u64, CachedDistribution>,
+    stats: CacheStats,
+}
+
+impl ComponentCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Solves a connected component (all of `cells` are unknown, `constraints` are the local
+    /// constraints touching them), consulting and then populating the cache by the
+    /// component's canonical structural signature.
+    fn solve(&mut self, cells: Vec<usize>, constraints: Vec<Constraint>) -> ComponentDistribution {
+        let (hash, canonical_order) = canonical_signature(&cells, &constraints);
+
+        if let Some(cached) = self.entries.get(&hash) {
+            self.stats.hits += 1;
+            let tallies = cached
+                .canonical_tallies
+                .iter()
+                .map(|(&k, canonical_tally)| {
+                    let mut tally = vec![0; cells.len()];
+                    for (rank, &i_cell) in canonical_order.iter().enumerate() {
+                        tally[i_cell] = canonical_tally[rank];
+                    }
+                    (k, tally)
+                })
+                .collect();
+            return ComponentDistribution {
+                cells,
+                counts: cached.counts.clone(),
+                tallies,
+            };
+        }
+
+        self.stats.misses += 1;
+        let (clauses, var_map) = generate_clauses(&cells, &constraints);
+
+        let mut counts: HashMap<usize, usize> = HashMap::new();
+        let mut tallies: HashMap<usize, Vec<usize>> = HashMap::new();
+        if let Ok(solutions) = crate::find_all_solutions(&clauses) {
+            for model in solutions {
+                let mine_set = decode_mine_set(&model, &var_map);
+                let k = cells.iter().filter(|idx| mine_set.contains(idx)).count();
+                *counts.entry(k).or_insert(0) += 1;
+                let tally = tallies.entry(k).or_insert_with(|| vec![0; cells.len()]);
+                for (i, idx) in cells.iter().enumerate() {
+                    if mine_set.contains(idx) {
+                        tally[i] += 1;
+                    }
+                }
+            }
+        }
+
+        let canonical_tallies: HashMap<usize, Vec<usize>> = tallies
+            .iter()
+            .map(|(&k, tally)| {
+                let canonical_tally = canonical_order.iter().map(|&i_cell| tally[i_cell]).collect();
+                (k, canonical_tally)
+            })
+            .collect();
+        self.entries.insert(
+            hash,
+            CachedDistribution {
+                counts: counts.clone(),
+                canonical_tallies,
+            },
+        );
+
+        ComponentDistribution {
+            cells,
+            counts,
+            tallies,
+        }
+    }
+}
+
+/// Finds cells whose mine/safe status is logically forced, using the solver's
+/// incremental/assumption interface instead of enumerating every model.
+///
+/// Builds the clause set once into a persistent solver, then for each unknown cell probes
+/// satisfiability twice under assumption: once assuming the cell's variable true (a mine)
+/// and once assuming it false (safe). If assuming "mine" is UNSAT the cell is provably safe;
+/// if assuming "safe" is UNSAT the cell is provably a mine; if both are SAT it is
+/// undetermined. The same solver instance is reused for every probe, so clauses it learns
+/// along the way are never thrown away.
+pub fn deduce_certain_cells(
+    unknown_indices: &[usize],
+    local_constraints: &[Constraint],
+) -> (HashSet<usize>, HashSet<usize>) {
+    let (clauses, var_map) = generate_clauses(unknown_indices, local_constraints);
+    let mut safe = HashSet::new();
+    let mut mine = HashSet::new();
+
+    let Ok(mut solver) = crate::find_all_solutions(&clauses) else {
+        return (safe, mine);
+    };
+
+    for (&cell_idx, &var) in &var_map {
+        solver.assume(&[var]);
+        let can_be_mine = solver.solve_once().is_some();
+
+        solver.assume(&[-var]);
+        let can_be_safe = solver.solve_once().is_some();
+
+        if !can_be_mine {
+            safe.insert(cell_idx);
+        } else if !can_be_safe {
+            mine.insert(cell_idx);
+        }
+    }
+
+    (safe, mine)
+}
+
+/// Multiplies two sparse "mine count -> weight" polynomials.
+fn convolve(a: &HashMap<usize, f64>, b: &HashMap<usize, f64>) -> HashMap<usize, f64> {
+    let mut out = HashMap::new();
+    for (&ka, &wa) in a {
+        for (&kb, &wb) in b {
+            *out.entry(ka + kb).or_insert(0.0) += wa * wb;
+        }
+    }
+    out
+}
+
+/// Combines independent components' mine-count distributions via convolution of their count
+/// polynomials, producing the same per-cell mine-probability weights (and the same overall
+/// normalization weight) that enumerating the full cross-product of all components' models
+/// would produce — without ever materializing that cross-product.
+///
+/// Returns `(border_cell_weight, sea_cell_weight, total_weight)`, where `sea_cell_weight` is
+/// already the per-cell share (every sea cell gets the same weight by symmetry).
+pub fn combine_components(
+    components: &[ComponentDistribution],
+    sea_size: usize,
+    global_mine_count: usize,
+) -> (HashMap<usize, f64>, f64, f64) {
+    let m = components.len();
+    let polys: Vec<HashMap<usize, f64>> = components
+        .iter()
+        .map(|comp| comp.counts.iter().map(|(&k, &n)| (k, n as f64)).collect())
+        .collect();
+
+    // prefix[i] = product of components[0..i]; suffix[i] = product of components[i..m].
+    let mut prefix = vec![HashMap::from([(0usize, 1.0)])];
+    for poly in &polys {
+        prefix.push(convolve(prefix.last().unwrap(), poly));
+    }
+    let mut suffix = vec![HashMap::from([(0usize, 1.0)])];
+    for poly in polys.iter().rev() {
+        suffix.push(convolve(suffix.last().unwrap(), poly));
+    }
+    suffix.reverse();
+
+    let weight_at = |t: usize, n: f64| -> f64 {
+        if global_mine_count < t {
+            return 0.0;
+        }
+        let remaining = global_mine_count - t;
+        if remaining > sea_size {
+            return 0.0;
+        }
+        n * combinations(sea_size as u128, remaining as u128) as f64
+    };
+
+    let mut cell_weight: HashMap<usize, f64> = HashMap::new();
+    for (i, comp) in components.iter().enumerate() {
+        // Everything except component `i`.
+        let others = convolve(&prefix[i], &suffix[i + 1]);
+        for (&k_c, tally) in &comp.tallies {
+            for (&s, &others_count) in &others {
+                let weight = weight_at(k_c + s, others_count);
+                if weight == 0.0 {
+                    continue;
+                }
+                for (i_cell, &cell_idx) in comp.cells.iter().enumerate() {
+                    if tally[i_cell] > 0 {
+                        *cell_weight.entry(cell_idx).or_insert(0.0) += tally[i_cell] as f64 * weight;
+                    }
+                }
+            }
+        }
+    }
+
+    let all = &prefix[m];
+    let mut total_weight = 0.0;
+    let mut sea_weight_total = 0.0;
+    for (&t, &n) in all {
+        if global_mine_count < t {
+            continue;
+        }
+        let remaining = global_mine_count - t;
+        if remaining > sea_size {
+            continue;
+        }
+        let w = weight_at(t, n);
+        total_weight += w;
+        sea_weight_total += remaining as f64 * w;
+    }
+    let sea_cell_weight = if sea_size > 0 {
+        sea_weight_total / sea_size as f64
+    } else {
+        0.0
+    };
+
+    (cell_weight, sea_cell_weight, total_weight)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::find_all_solutions;
     use minesweeper_rs::game::Game;
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
 
     #[test]
     fn test_simple_solver() {
@@ -308,4 +825,156 @@ mod tests {
         });
         assert!(has_expected_solution);
     }
+
+    #[test]
+    fn test_propagate_trivial_deductions_resolves_and_cascades() {
+        // Constraint A settles cells 1 and 2 as safe outright (count 0). Constraint B shares
+        // cell 2 with A; once 2 is known safe it drops out of B, leaving B's remaining cell 3
+        // alone against a count of 1 — forcing 3 to be a mine on the next pass.
+        let constraints = vec![
+            Constraint {
+                cells: vec![1, 2],
+                count: 0.0,
+            },
+            Constraint {
+                cells: vec![2, 3],
+                count: 1.0,
+            },
+        ];
+        let unknown_indices = vec![1, 2, 3];
+        let propagation = propagate_trivial_deductions(&unknown_indices, &constraints);
+
+        assert_eq!(propagation.safe, HashSet::from([1, 2]));
+        assert_eq!(propagation.mine, HashSet::from([3]));
+        assert!(propagation.residual_unknowns.is_empty());
+        assert!(propagation.residual_constraints.is_empty());
+    }
+
+    #[test]
+    fn test_decompose_and_solve_splits_independent_components() {
+        // Two "exactly one of two cells" constraints over disjoint cells form two separate
+        // connected components, so each should be solved (and counted) independently.
+        let constraints = vec![
+            Constraint {
+                cells: vec![10, 11],
+                count: 1.0,
+            },
+            Constraint {
+                cells: vec![20, 21],
+                count: 1.0,
+            },
+        ];
+        let unknown_indices = vec![10, 11, 20, 21];
+        let mut cache = ComponentCache::new();
+        let components = decompose_and_solve(&unknown_indices, &constraints, &mut cache);
+
+        assert_eq!(components.len(), 2);
+        for component in &components {
+            assert_eq!(component.cells.len(), 2);
+            assert_eq!(component.counts.get(&1), Some(&2));
+        }
+    }
+
+    #[test]
+    fn test_decompose_and_solve_folds_trivial_deductions_alongside_components() {
+        // Cells 1 and 2 are resolved for free by propagation; cells 3 and 4 form a genuine
+        // component that still needs solving.
+        let constraints = vec![
+            Constraint {
+                cells: vec![1, 2],
+                count: 0.0,
+            },
+            Constraint {
+                cells: vec![3, 4],
+                count: 1.0,
+            },
+        ];
+        let unknown_indices = vec![1, 2, 3, 4];
+        let mut cache = ComponentCache::new();
+        let components = decompose_and_solve(&unknown_indices, &constraints, &mut cache);
+
+        assert_eq!(components.len(), 3);
+        let forced: Vec<_> = components.iter().filter(|c| c.cells.len() == 1).collect();
+        assert_eq!(forced.len(), 2);
+        assert!(forced.iter().all(|c| c.counts.get(&0) == Some(&1)));
+        let solved = components
+            .iter()
+            .find(|c| c.cells.len() == 2)
+            .expect("cells 3 and 4 form one unresolved component");
+        assert_eq!(solved.counts.get(&1), Some(&2));
+    }
+
+    #[test]
+    fn test_component_cache_hits_on_structurally_identical_components() {
+        // Same shape (two cells, "exactly one is a mine"), different absolute cell indices —
+        // the canonical signature should still recognize them as the same structure.
+        let mut cache = ComponentCache::new();
+        cache.solve(
+            vec![1, 2],
+            vec![Constraint {
+                cells: vec![1, 2],
+                count: 1.0,
+            }],
+        );
+        assert_eq!(cache.stats().misses, 1);
+        assert_eq!(cache.stats().hits, 0);
+
+        cache.solve(
+            vec![100, 101],
+            vec![Constraint {
+                cells: vec![100, 101],
+                count: 1.0,
+            }],
+        );
+        assert_eq!(cache.stats().misses, 1);
+        assert_eq!(cache.stats().hits, 1);
+    }
+
+    #[test]
+    fn test_deduce_certain_cells_via_incremental_assumptions() {
+        let constraints = vec![
+            Constraint {
+                cells: vec![1],
+                count: 1.0,
+            },
+            Constraint {
+                cells: vec![2],
+                count: 0.0,
+            },
+        ];
+        let unknown_indices = vec![1, 2, 3];
+        let (safe, mine) = deduce_certain_cells(&unknown_indices, &constraints);
+
+        assert!(mine.contains(&1));
+        assert!(safe.contains(&2));
+        // Cell 3 isn't touched by any constraint, so it's neither provably safe nor a mine.
+        assert!(!safe.contains(&3) && !mine.contains(&3));
+    }
+
+    #[test]
+    fn test_combine_components_matches_hand_computed_weights() {
+        // Two independent components, each "exactly one of two cells is a mine" (2 equally
+        // likely solutions apiece), with no sea and a global mine count that forces both
+        // components to their single-mine case.
+        let components = vec![
+            ComponentDistribution {
+                cells: vec![1, 2],
+                counts: HashMap::from([(1, 2)]),
+                tallies: HashMap::from([(1, vec![1, 1])]),
+            },
+            ComponentDistribution {
+                cells: vec![3, 4],
+                counts: HashMap::from([(1, 2)]),
+                tallies: HashMap::from([(1, vec![1, 1])]),
+            },
+        ];
+
+        let (cell_weight, sea_cell_weight, total_weight) = combine_components(&components, 0, 2);
+
+        for cell in [1, 2, 3, 4] {
+            assert_eq!(cell_weight[&cell], 2.0);
+        }
+        assert_eq!(total_weight, 4.0);
+        assert_eq!(sea_cell_weight, 0.0);
+    }
 }
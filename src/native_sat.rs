@@ -0,0 +1,197 @@
+//! A from-scratch, dependency-free alternative to the `varisat`/`splr` backends: a relational
+//! depth-first search over the same DIMACS-style clause sets every puzzle module already
+//! produces, offered so solver strategies can be compared on identical input.
+//!
+//! In the spirit of miniKanren's goal/stream model, a partial assignment is an immutable
+//! snapshot of the search so far; extending it with a decision on the next variable is a goal
+//! that either succeeds (producing a new, larger partial assignment) or fails outright. The
+//! iterator below interleaves the two branches of each decision (try `true`, then `false`) via
+//! an explicit stack of choice points rather than native recursion, so the search is resumable:
+//! each call to `next()` runs the DFS forward until it reaches a full, consistent assignment,
+//! yields it, and leaves the stack positioned to resume the search for the next one.
+use varisat::Lit;
+
+/// Not-yet-decided (0), `true` (1) or `false` (-1) for one variable.
+type Assignment = i8;
+
+/// How far a choice point's exploration has progressed: which branch (if any) remains to be
+/// tried once the search backtracks to it.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum NextBranch {
+    False,
+    Exhausted,
+}
+
+struct ChoicePoint {
+    var: usize,
+    next_branch: NextBranch,
+}
+
+/// Enumerates every satisfying assignment of a clause set via depth-first search with
+/// chronological backtracking, pruning a branch as soon as some clause is fully assigned and
+/// false under it (forward checking) rather than waiting for a full assignment.
+pub struct NativeSolutionIterator {
+    clauses: Vec<Vec<isize>>,
+    num_vars: usize,
+    assignment: Vec<Assignment>,
+    choices: Vec<ChoicePoint>,
+    finished: bool,
+}
+
+impl NativeSolutionIterator {
+    fn new(clauses: Vec<Vec<isize>>) -> Self {
+        let num_vars = clauses
+            .iter()
+            .flat_map(|clause| clause.iter())
+            .map(|&lit| lit.unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        NativeSolutionIterator {
+            clauses,
+            num_vars,
+            assignment: vec![0; num_vars + 1],
+            choices: Vec::new(),
+            finished: false,
+        }
+    }
+
+    /// `true` iff no clause is fully assigned and false under the current (partial) assignment.
+    fn is_consistent(&self) -> bool {
+        self.clauses.iter().all(|clause| {
+            let mut all_assigned = true;
+            for &lit in clause {
+                let var = lit.unsigned_abs();
+                let value = self.assignment[var];
+                if value == 0 {
+                    all_assigned = false;
+                    continue;
+                }
+                let literal_true = (lit > 0) == (value == 1);
+                if literal_true {
+                    return true; // clause already satisfied
+                }
+            }
+            !all_assigned // only a conflict once every literal is assigned and false
+        })
+    }
+
+    /// Tries assigning `var` to `value`; rolls the assignment back and returns `false` if doing
+    /// so would immediately falsify some clause.
+    fn try_assign(&mut self, var: usize, value: bool) -> bool {
+        self.assignment[var] = if value { 1 } else { -1 };
+        if self.is_consistent() {
+            true
+        } else {
+            self.assignment[var] = 0;
+            false
+        }
+    }
+
+    /// Undoes choice points until one still has an untried branch, tries it, and returns
+    /// `true` — or returns `false` once the whole search space is exhausted.
+    fn backtrack(&mut self) -> bool {
+        while let Some(point) = self.choices.pop() {
+            self.assignment[point.var] = 0;
+            if point.next_branch == NextBranch::False && self.try_assign(point.var, false) {
+                self.choices.push(ChoicePoint {
+                    var: point.var,
+                    next_branch: NextBranch::Exhausted,
+                });
+                return true;
+            }
+        }
+        false
+    }
+
+    fn current_model(&self) -> Vec<Lit> {
+        (1..=self.num_vars)
+            .map(|var| Lit::from_dimacs(if self.assignment[var] == 1 {
+                var as isize
+            } else {
+                -(var as isize)
+            }))
+            .collect()
+    }
+}
+
+impl Iterator for NativeSolutionIterator {
+    type Item = Vec<Lit>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        while self.choices.len() < self.num_vars {
+            let var = self.choices.len() + 1;
+            if self.try_assign(var, true) {
+                self.choices.push(ChoicePoint {
+                    var,
+                    next_branch: NextBranch::False,
+                });
+                continue;
+            }
+            if self.try_assign(var, false) {
+                self.choices.push(ChoicePoint {
+                    var,
+                    next_branch: NextBranch::Exhausted,
+                });
+                continue;
+            }
+            if !self.backtrack() {
+                self.finished = true;
+                return None;
+            }
+        }
+
+        let model = self.current_model();
+        if !self.backtrack() {
+            self.finished = true;
+        }
+        Some(model)
+    }
+}
+
+/// Enumerates every solution to `clauses` using the native backtracking search, mirroring
+/// `find_all_solutions`'s interface so callers can swap between the two engines freely.
+pub fn find_all_solutions(clauses: &[Vec<isize>]) -> NativeSolutionIterator {
+    NativeSolutionIterator::new(clauses.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn model_to_map(model: &[Lit]) -> HashMap<isize, bool> {
+        model
+            .iter()
+            .map(|lit| (lit.var().to_dimacs(), lit.is_positive()))
+            .collect()
+    }
+
+    #[test]
+    fn test_trivial_unit_clauses() {
+        let clauses = vec![vec![1], vec![-2]];
+        let model = find_all_solutions(&clauses).next().unwrap();
+        let assignment = model_to_map(&model);
+        assert!(assignment[&1]);
+        assert!(!assignment[&2]);
+    }
+
+    #[test]
+    fn test_unsatisfiable_returns_none() {
+        let clauses = vec![vec![1], vec![-1]];
+        assert!(find_all_solutions(&clauses).next().is_none());
+    }
+
+    #[test]
+    fn test_enumerates_every_solution_of_an_at_most_one_pair() {
+        // (x1 OR x2) AND (-x1 OR -x2): exactly one of x1, x2 is true.
+        let clauses = vec![vec![1, 2], vec![-1, -2]];
+        let solutions: Vec<_> = find_all_solutions(&clauses)
+            .map(|model| model_to_map(&model))
+            .collect();
+        assert_eq!(solutions.len(), 2);
+        assert!(solutions.iter().all(|s| s[&1] != s[&2]));
+    }
+}
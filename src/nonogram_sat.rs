@@ -0,0 +1,227 @@
+use std::fmt;
+use varisat::Lit;
+
+/// The lengths of the consecutive black runs in one row or column, given in order.
+pub type Clue = Vec<usize>;
+
+/// A nonogram puzzle: the clue for every row and every column.
+pub struct Puzzle {
+    pub rows: Vec<Clue>,
+    pub cols: Vec<Clue>,
+}
+
+impl Puzzle {
+    pub fn new(rows: Vec<Clue>, cols: Vec<Clue>) -> Self {
+        Puzzle { rows, cols }
+    }
+
+    fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn num_cols(&self) -> usize {
+        self.cols.len()
+    }
+}
+
+/// A solved nonogram grid. `true` means the cell is black.
+pub struct Nonogram {
+    cells: Vec<Vec<bool>>,
+}
+
+impl fmt::Display for Nonogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.cells {
+            for &black in row {
+                write!(f, "{}", if black { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Helper to map a 0-indexed (row, col) to a 1-indexed DIMACS variable number.
+/// A variable is true if cell (r, c) is black.
+fn cell_var(r: usize, c: usize, num_cols: usize) -> isize {
+    (r * num_cols + c + 1) as isize
+}
+
+/// Computes the feasible (leftmost, rightmost) 0-indexed start position of each block
+/// in a line of length `n`, given the block lengths in order.
+fn block_ranges(n: usize, lens: &[usize]) -> Vec<(usize, usize)> {
+    let k = lens.len();
+    let mut ranges = vec![(0usize, 0usize); k];
+
+    let mut leftmost = 0usize;
+    for i in 0..k {
+        ranges[i].0 = leftmost;
+        leftmost += lens[i] + 1;
+    }
+
+    // suffix is the minimal length needed strictly after the block currently being visited.
+    let mut suffix = 0usize;
+    for i in (0..k).rev() {
+        ranges[i].1 = n - lens[i] - suffix;
+        suffix += lens[i] + 1;
+    }
+
+    ranges
+}
+
+/// Emits the block-placement CNF for a single line (row or column) of `n` cells with the
+/// given run lengths. `line_var(i)` is the shared per-cell "black" variable for position
+/// `i` along the line; fresh block-start variables are allocated through `next_var`.
+fn emit_line_clauses(
+    n: usize,
+    lens: &[usize],
+    line_var: impl Fn(usize) -> isize,
+    next_var: &mut isize,
+    clauses: &mut Vec<Vec<isize>>,
+) {
+    if lens.is_empty() {
+        // No blocks: every cell on this line must be white.
+        for c in 0..n {
+            clauses.push(vec![-line_var(c)]);
+        }
+        return;
+    }
+
+    let ranges = block_ranges(n, lens);
+    let k = lens.len();
+
+    // Allocate one start variable per feasible position of each block.
+    let starts: Vec<Vec<isize>> = ranges
+        .iter()
+        .map(|&(lo, hi)| {
+            (lo..=hi)
+                .map(|_| {
+                    let v = *next_var;
+                    *next_var += 1;
+                    v
+                })
+                .collect()
+        })
+        .collect();
+
+    // --- At-least-one start position per block ---
+    for vars in &starts {
+        clauses.push(vars.clone());
+    }
+
+    // --- At-most-one start position per block (pairwise) ---
+    for vars in &starts {
+        for i in 0..vars.len() {
+            for j in (i + 1)..vars.len() {
+                clauses.push(vec![-vars[i], -vars[j]]);
+            }
+        }
+    }
+
+    // --- Ordering: block i starting at p forces block i+1 to start at >= p + l_i + 1 ---
+    for i in 0..k - 1 {
+        let (lo_i, _) = ranges[i];
+        let (lo_next, _) = ranges[i + 1];
+        for (pi, &var_i) in starts[i].iter().enumerate() {
+            let p = lo_i + pi;
+            let min_next = p + lens[i] + 1;
+            for (pj, &var_next) in starts[i + 1].iter().enumerate() {
+                let p_next = lo_next + pj;
+                if p_next < min_next {
+                    clauses.push(vec![-var_i, -var_next]);
+                }
+            }
+        }
+    }
+
+    // --- Coverage: black_c <-> OR of placements that cover cell c ---
+    for c in 0..n {
+        let mut covering = Vec::new();
+        for (i, vars) in starts.iter().enumerate() {
+            let (lo, _) = ranges[i];
+            for (pi, &var_i) in vars.iter().enumerate() {
+                let p = lo + pi;
+                if p <= c && c < p + lens[i] {
+                    covering.push(var_i);
+                }
+            }
+        }
+
+        let cell = line_var(c);
+        // black_c -> OR(covering)
+        let mut clause = vec![-cell];
+        clause.extend(&covering);
+        clauses.push(clause);
+        // each covering placement -> black_c
+        for &v in &covering {
+            clauses.push(vec![-v, cell]);
+        }
+    }
+}
+
+/// Generates the CNF clauses for a nonogram puzzle.
+pub fn generate_clauses(puzzle: &Puzzle) -> Vec<Vec<isize>> {
+    let num_rows = puzzle.num_rows();
+    let num_cols = puzzle.num_cols();
+    let mut clauses = Vec::new();
+    let mut next_var = (num_rows * num_cols + 1) as isize;
+
+    for (r, clue) in puzzle.rows.iter().enumerate() {
+        emit_line_clauses(
+            num_cols,
+            clue,
+            |c| cell_var(r, c, num_cols),
+            &mut next_var,
+            &mut clauses,
+        );
+    }
+
+    for (c, clue) in puzzle.cols.iter().enumerate() {
+        emit_line_clauses(
+            num_rows,
+            clue,
+            |r| cell_var(r, c, num_cols),
+            &mut next_var,
+            &mut clauses,
+        );
+    }
+
+    clauses
+}
+
+/// Decodes a SAT model into a filled grid, reading only the per-cell `black` literals.
+/// Block-start variables and other auxiliaries are ignored since they fall outside the
+/// `1..=num_rows*num_cols` range.
+pub fn decode_solution(model: &[Lit], num_rows: usize, num_cols: usize) -> Nonogram {
+    let mut cells = vec![vec![false; num_cols]; num_rows];
+    let max_cell_var = (num_rows * num_cols) as isize;
+    for &lit in model.iter() {
+        if lit.is_positive() {
+            let var = lit.var().to_dimacs();
+            if var <= max_cell_var {
+                let idx = (var - 1) as usize;
+                cells[idx / num_cols][idx % num_cols] = true;
+            }
+        }
+    }
+    Nonogram { cells }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fully_black_2x2() {
+        let puzzle = Puzzle::new(vec![vec![2], vec![2]], vec![vec![2], vec![2]]);
+        let clauses = generate_clauses(&puzzle);
+
+        let solutions: Vec<Nonogram> = crate::find_all_solutions(&clauses)
+            .unwrap()
+            .map(|model| decode_solution(&model, 2, 2))
+            .collect();
+
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(format!("{}", solutions[0]), "##\n##\n");
+    }
+}
@@ -0,0 +1,310 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use varisat::Lit;
+
+/// Which cells count as "adjacent" when linking consecutive values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjacency {
+    /// Numbrix: up/down/left/right only.
+    VonNeumann,
+    /// Hidato: the von Neumann neighbours plus the four diagonals.
+    Moore,
+}
+
+impl Adjacency {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Adjacency::VonNeumann => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            Adjacency::Moore => &[
+                (-1, 0),
+                (1, 0),
+                (0, -1),
+                (0, 1),
+                (-1, -1),
+                (-1, 1),
+                (1, -1),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// An N x N grid of clues. 0 represents an empty cell.
+pub struct NumbrixGrid {
+    n: usize,
+    cells: Vec<Vec<usize>>,
+}
+
+#[derive(Debug)]
+pub enum NumbrixParseError {
+    IoError(io::Error),
+    InvalidFormat(String),
+}
+
+impl From<io::Error> for NumbrixParseError {
+    fn from(error: io::Error) -> Self {
+        NumbrixParseError::IoError(error)
+    }
+}
+
+impl Error for NumbrixParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NumbrixParseError::IoError(e) => Some(e),
+            NumbrixParseError::InvalidFormat(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for NumbrixParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumbrixParseError::IoError(e) => write!(f, "IO error: {e}"),
+            NumbrixParseError::InvalidFormat(msg) => write!(f, "Invalid format: {msg}"),
+        }
+    }
+}
+
+impl NumbrixGrid {
+    /// Parse a Numbrix puzzle from whitespace-separated rows of numbers (0 for empty),
+    /// with the grid dimension inferred from the number of rows.
+    pub fn from_text(text: &str) -> Result<Self, NumbrixParseError> {
+        let rows: Vec<Vec<usize>> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|tok| {
+                        tok.parse::<usize>().map_err(|_| {
+                            NumbrixParseError::InvalidFormat(format!("Invalid number '{tok}'"))
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let n = rows.len();
+        if n == 0 || rows.iter().any(|row| row.len() != n) {
+            return Err(NumbrixParseError::InvalidFormat(format!(
+                "Expected a square {n}x{n} grid"
+            )));
+        }
+
+        Ok(NumbrixGrid { n, cells: rows })
+    }
+
+    /// Read a Numbrix puzzle from a file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, NumbrixParseError> {
+        let content = fs::read_to_string(path)?;
+        Self::from_text(&content)
+    }
+
+    /// The grid dimension `n` (the board is `n x n`, values run `1..=n*n`).
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// The clue at `(r, c)`, or 0 if the cell is empty.
+    pub fn get(&self, r: usize, c: usize) -> usize {
+        self.cells[r][c]
+    }
+
+    /// Sets the clue at `(r, c)` (0 clears the cell).
+    pub fn set(&mut self, r: usize, c: usize, value: usize) {
+        self.cells[r][c] = value;
+    }
+
+    /// An empty `n x n` grid with no clues.
+    pub fn empty(n: usize) -> Self {
+        NumbrixGrid {
+            n,
+            cells: vec![vec![0; n]; n],
+        }
+    }
+}
+
+/// Helper to map a 0-indexed (row, col, value) to a 1-indexed DIMACS variable number.
+/// A variable is true if cell (r, c) holds `value`. Values are 1..=n*n.
+fn coords_to_var(r: usize, c: usize, value: usize, n: usize) -> isize {
+    (r * n * n * n + c * n * n + (value - 1) + 1) as isize
+}
+
+/// Helper to map a 1-indexed DIMACS variable number back to 0-indexed (row, col, value).
+fn var_to_coords(var: usize, n: usize) -> (usize, usize, usize) {
+    let zero_based_var = var - 1;
+    let r = zero_based_var / (n * n * n);
+    let c = (zero_based_var / (n * n)) % n;
+    let value = (zero_based_var % (n * n)) + 1;
+    (r, c, value)
+}
+
+/// Generates the CNF clauses for a Numbrix/Hidato puzzle.
+pub fn generate_clauses(grid: &NumbrixGrid, adjacency: Adjacency) -> Vec<Vec<isize>> {
+    let n = grid.n;
+    let max_value = n * n;
+    let mut clauses = Vec::new();
+
+    // --- CONSTRAINT 1: Each cell holds at least one value ---
+    for r in 0..n {
+        for c in 0..n {
+            clauses.push((1..=max_value).map(|v| coords_to_var(r, c, v, n)).collect());
+        }
+    }
+
+    // --- CONSTRAINT 2: Each cell holds at most one value ---
+    for r in 0..n {
+        for c in 0..n {
+            for v1 in 1..=max_value {
+                for v2 in (v1 + 1)..=max_value {
+                    clauses.push(vec![
+                        -coords_to_var(r, c, v1, n),
+                        -coords_to_var(r, c, v2, n),
+                    ]);
+                }
+            }
+        }
+    }
+
+    // --- CONSTRAINT 3: Each value occupies at most one cell ---
+    let cells: Vec<(usize, usize)> = (0..n).flat_map(|r| (0..n).map(move |c| (r, c))).collect();
+    for v in 1..=max_value {
+        for i in 0..cells.len() {
+            for j in (i + 1)..cells.len() {
+                let (r1, c1) = cells[i];
+                let (r2, c2) = cells[j];
+                clauses.push(vec![
+                    -coords_to_var(r1, c1, v, n),
+                    -coords_to_var(r2, c2, v, n),
+                ]);
+            }
+        }
+    }
+
+    // --- CONSTRAINT 4: Consecutive values occupy adjacent cells ---
+    // For every value v and cell c: x_{c,v} -> OR of x_{c',v+1} over neighbours c' of c.
+    for r in 0..n {
+        for c in 0..n {
+            let neighbours: Vec<(usize, usize)> = adjacency
+                .offsets()
+                .iter()
+                .filter_map(|&(dr, dc)| {
+                    let nr = r as isize + dr;
+                    let nc = c as isize + dc;
+                    if nr >= 0 && nr < n as isize && nc >= 0 && nc < n as isize {
+                        Some((nr as usize, nc as usize))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            for v in 1..max_value {
+                let mut clause = vec![-coords_to_var(r, c, v, n)];
+                clause.extend(
+                    neighbours
+                        .iter()
+                        .map(|&(nr, nc)| coords_to_var(nr, nc, v + 1, n)),
+                );
+                clauses.push(clause);
+            }
+        }
+    }
+
+    // --- CONSTRAINT 5: Pre-filled clues ---
+    for r in 0..n {
+        for c in 0..n {
+            let v = grid.cells[r][c];
+            if v != 0 {
+                clauses.push(vec![coords_to_var(r, c, v, n)]);
+            }
+        }
+    }
+
+    clauses
+}
+
+/// A solved Numbrix/Hidato grid.
+pub struct Numbrix {
+    n: usize,
+    cells: Vec<Vec<usize>>,
+}
+
+/// Decodes a SAT model into a filled grid.
+pub fn decode_solution(model: &[Lit], n: usize) -> Numbrix {
+    let mut cells = vec![vec![0usize; n]; n];
+    for &lit in model.iter() {
+        if lit.is_positive() {
+            let (r, c, v) = var_to_coords(lit.var().to_dimacs() as usize, n);
+            cells[r][c] = v;
+        }
+    }
+    Numbrix { n, cells }
+}
+
+impl From<Numbrix> for NumbrixGrid {
+    /// Treats a solved grid as a (fully-clued) puzzle grid, so a solution can be fed back
+    /// through the same clue-removal machinery that operates on `NumbrixGrid`.
+    fn from(solved: Numbrix) -> Self {
+        NumbrixGrid {
+            n: solved.n,
+            cells: solved.cells,
+        }
+    }
+}
+
+impl fmt::Display for Numbrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = (self.n * self.n).to_string().len();
+        for row in &self.cells {
+            for (c, &v) in row.iter().enumerate() {
+                if c > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{v:width$}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_text_square() {
+        let input = "
+            0 0 0
+            0 0 0
+            1 0 9";
+        let grid = NumbrixGrid::from_text(input).unwrap();
+        assert_eq!(grid.n, 3);
+    }
+
+    #[test]
+    fn test_solves_small_path() {
+        // A 3x3 spiral: 1 2 3 / 8 9 4 / 7 6 5, with enough clues to pin the direction.
+        let grid = NumbrixGrid::from_text(
+            "
+            1 2 0
+            0 9 0
+            0 0 5",
+        )
+        .unwrap();
+        let clauses = generate_clauses(&grid, Adjacency::VonNeumann);
+        let solutions: Vec<Numbrix> = crate::find_all_solutions(&clauses)
+            .unwrap()
+            .map(|model| decode_solution(&model, 3))
+            .collect();
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(
+            solutions[0].cells,
+            vec![vec![1, 2, 3], vec![8, 9, 4], vec![7, 6, 5]]
+        );
+    }
+}
@@ -0,0 +1,595 @@
+//! A small, self-contained Sudoku solver for the `satpuzzles` CLI's `sudoku` subcommand.
+//!
+//! `sudoku_sat`'s `Constraint` trait exists to let callers layer in variants (diagonals,
+//! anti-knight, etc.); this module only ever encodes the classic row/column/box regions, so it
+//! keeps its own fixed clause generation instead of paying for a pluggable-constraint API it
+//! would never plug anything into. Built around a handful of bundled 9x9 example puzzles plus
+//! the ability to parse a puzzle of any `box_size` supplied by the user.
+use crate::generator::{Difficulty, has_unique_solution};
+use anyhow::Result;
+use rand::seq::SliceRandom;
+use std::error::Error;
+use std::fmt;
+use varisat::Lit;
+
+/// A Sudoku grid of dimension `box_size^2 x box_size^2`, holding values `1..=box_size^2` (0
+/// for an empty cell), stored row-major. `box_size` is a runtime property of each grid, so the
+/// same type and encoder handle 9x9, 16x16, 25x25 and larger boards alike.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SudokuGrid {
+    box_size: usize,
+    cells: Vec<u16>,
+}
+
+#[derive(Debug)]
+pub enum SudokuParseError {
+    InvalidFormat(String),
+    Inconsistent(String),
+}
+
+impl Error for SudokuParseError {}
+
+impl fmt::Display for SudokuParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SudokuParseError::InvalidFormat(msg) => write!(f, "Invalid format: {msg}"),
+            SudokuParseError::Inconsistent(msg) => write!(f, "Inconsistent puzzle: {msg}"),
+        }
+    }
+}
+
+/// Given a cell count, infers the box size `b` such that the count is exactly `(b*b)^2`
+/// cells — i.e. a `b*b x b*b` grid. Returns `None` if the count doesn't form such a square.
+fn infer_box_size(cell_count: usize) -> Option<usize> {
+    let n = (cell_count as f64).sqrt().round() as usize;
+    if n * n != cell_count || n == 0 {
+        return None;
+    }
+    let b = (n as f64).sqrt().round() as usize;
+    if b * b != n { None } else { Some(b) }
+}
+
+/// Parses a single cell's text representation: `.` or `0` for empty, `1`-`9` for a digit, and
+/// (for boards wider than 9) `A`-`Z` for digits 10-35.
+fn parse_cell_token(c: char) -> Result<u16, SudokuParseError> {
+    match c {
+        '.' | '0' => Ok(0),
+        '1'..='9' => Ok(c as u16 - '0' as u16),
+        c if c.is_ascii_uppercase() => Ok((c as u16) - ('A' as u16) + 10),
+        c if c.is_ascii_lowercase() => Ok((c.to_ascii_uppercase() as u16) - ('A' as u16) + 10),
+        other => Err(SudokuParseError::InvalidFormat(format!(
+            "Invalid cell value '{other}'"
+        ))),
+    }
+}
+
+impl SudokuGrid {
+    /// Creates an empty grid of the given box size (side length `box_size^2`).
+    pub fn empty(box_size: usize) -> Self {
+        let n = box_size * box_size;
+        SudokuGrid {
+            box_size,
+            cells: vec![0; n * n],
+        }
+    }
+
+    /// Parses a puzzle from text, auto-detecting the format: the coordinate-triple format (a
+    /// `rows,cols` header followed by one `row,col,value` line per given, 0-based coordinates,
+    /// 1-based value), or the compact one-character-per-cell form (`.`/`0` for empty, `1`-`9`
+    /// then `A`-`Z` for a digit, whitespace ignored). Either way, the parsed givens are
+    /// validated against the row/column/box constraints before being accepted.
+    pub fn parse(text: &str) -> Result<Self, SudokuParseError> {
+        let first_line = text.lines().map(str::trim).find(|l| !l.is_empty());
+        let grid = if first_line.is_some_and(|l| l.split(',').count() == 2) {
+            Self::from_triples(text)?
+        } else {
+            Self::from_compact(text)?
+        };
+        grid.validate()?;
+        Ok(grid)
+    }
+
+    fn from_compact(text: &str) -> Result<Self, SudokuParseError> {
+        let digits: Vec<u16> = text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(parse_cell_token)
+            .collect::<Result<_, _>>()?;
+
+        let box_size = infer_box_size(digits.len()).ok_or_else(|| {
+            SudokuParseError::InvalidFormat(format!(
+                "{} cells don't form a box_size^2 x box_size^2 grid",
+                digits.len()
+            ))
+        })?;
+        Ok(SudokuGrid {
+            box_size,
+            cells: digits,
+        })
+    }
+
+    fn from_triples(text: &str) -> Result<Self, SudokuParseError> {
+        let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+        let header = lines
+            .next()
+            .ok_or_else(|| SudokuParseError::InvalidFormat("missing dimension header".into()))?;
+        let (rows, cols) = header
+            .split_once(',')
+            .and_then(|(r, c)| Some((r.trim().parse::<usize>().ok()?, c.trim().parse().ok()?)))
+            .ok_or_else(|| {
+                SudokuParseError::InvalidFormat(format!("invalid dimension header '{header}'"))
+            })?;
+        if rows != cols {
+            return Err(SudokuParseError::InvalidFormat(format!(
+                "grid must be square, got {rows},{cols}"
+            )));
+        }
+        let box_size = infer_box_size(rows * cols).ok_or_else(|| {
+            SudokuParseError::InvalidFormat(format!(
+                "{rows},{cols} isn't a box_size^2 x box_size^2 grid"
+            ))
+        })?;
+        let n = rows;
+
+        let mut grid = SudokuGrid::empty(box_size);
+        for line in lines {
+            let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [r, c, v] = parts.as_slice() else {
+                return Err(SudokuParseError::InvalidFormat(format!(
+                    "expected 'row,col,value', got '{line}'"
+                )));
+            };
+            let parse = |s: &str, what: &str| {
+                s.parse::<usize>().map_err(|_| {
+                    SudokuParseError::InvalidFormat(format!("invalid {what} in '{line}'"))
+                })
+            };
+            let r = parse(r, "row")?;
+            let c = parse(c, "column")?;
+            let v = parse(v, "value")?;
+            if r >= n || c >= n {
+                return Err(SudokuParseError::InvalidFormat(format!(
+                    "coordinate ({r}, {c}) is outside the {n}x{n} grid"
+                )));
+            }
+            if v > n {
+                return Err(SudokuParseError::InvalidFormat(format!(
+                    "value {v} exceeds grid dimension {n}"
+                )));
+            }
+            grid.set(r, c, v as u16);
+        }
+        Ok(grid)
+    }
+
+    /// Checks that the givens don't already violate the row/column/box constraints.
+    fn validate(&self) -> Result<(), SudokuParseError> {
+        let n = self.n();
+        let check_group = |cells: Vec<u16>, what: &str, idx: usize| {
+            let mut seen = vec![false; n + 1];
+            for &d in &cells {
+                if d == 0 {
+                    continue;
+                }
+                if seen[d as usize] {
+                    return Err(SudokuParseError::Inconsistent(format!(
+                        "digit {d} repeats in {what} {idx}"
+                    )));
+                }
+                seen[d as usize] = true;
+            }
+            Ok(())
+        };
+
+        for r in 0..n {
+            check_group((0..n).map(|c| self.get(r, c)).collect(), "row", r)?;
+        }
+        for c in 0..n {
+            check_group((0..n).map(|r| self.get(r, c)).collect(), "column", c)?;
+        }
+        for br in 0..self.box_size {
+            for bc in 0..self.box_size {
+                let cells = (0..self.box_size)
+                    .flat_map(|ro| (0..self.box_size).map(move |co| (ro, co)))
+                    .map(|(ro, co)| self.get(br * self.box_size + ro, bc * self.box_size + co))
+                    .collect();
+                check_group(cells, "box", br * self.box_size + bc)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The grid's box size (side length is `box_size^2`).
+    pub fn box_size(&self) -> usize {
+        self.box_size
+    }
+
+    /// The grid's side length, `box_size^2`.
+    pub fn n(&self) -> usize {
+        self.box_size * self.box_size
+    }
+
+    /// The digit at `(r, c)`, or 0 if the cell is empty.
+    pub fn get(&self, r: usize, c: usize) -> u16 {
+        self.cells[r * self.n() + c]
+    }
+
+    /// Sets the digit at `(r, c)` (0 clears the cell).
+    pub fn set(&mut self, r: usize, c: usize, digit: u16) {
+        let n = self.n();
+        self.cells[r * n + c] = digit;
+    }
+}
+
+impl fmt::Display for SudokuGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.n();
+        let width = if n > 9 { 2 } else { 1 };
+        for r in 0..n {
+            for c in 0..n {
+                match self.get(r, c) {
+                    0 => write!(f, "{:>width$} ", ".")?,
+                    d if d <= 9 => write!(f, "{d:>width$} ")?,
+                    d => write!(f, "{:>width$} ", (b'A' + (d - 10) as u8) as char)?,
+                }
+                if c % self.box_size == self.box_size - 1 && c != n - 1 {
+                    write!(f, "| ")?;
+                }
+            }
+            writeln!(f)?;
+            if r % self.box_size == self.box_size - 1 && r != n - 1 {
+                writeln!(f, "{}", "-".repeat((n + self.box_size - 1) * (width + 2)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A classic, mostly-filled "easy" 9x9 example puzzle.
+pub fn puzzle_easy() -> SudokuGrid {
+    SudokuGrid::parse(
+        "530070000\
+         600195000\
+         098000060\
+         800060003\
+         400803001\
+         700020006\
+         060000280\
+         000419005\
+         000080079",
+    )
+    .expect("bundled puzzle is well-formed")
+}
+
+/// A sparser 9x9 example with fewer givens.
+pub fn puzzle_harder() -> SudokuGrid {
+    SudokuGrid::parse(
+        "800000000\
+         003600000\
+         070090200\
+         050007000\
+         000045700\
+         000100030\
+         001000068\
+         008500010\
+         090000400",
+    )
+    .expect("bundled puzzle is well-formed")
+}
+
+/// "AI Escargot", one of the sparser widely-cited 9x9 example puzzles.
+pub fn puzzle_hard() -> SudokuGrid {
+    SudokuGrid::parse(
+        "100007090\
+         030020008\
+         009600500\
+         005300900\
+         010080002\
+         600004000\
+         300000010\
+         040000007\
+         007000300",
+    )
+    .expect("bundled puzzle is well-formed")
+}
+
+/// Helper to map a 0-indexed (row, col, digit) to a 1-indexed DIMACS variable number. A
+/// variable is true if cell (r, c) contains digit d, which is `1..=n`.
+fn coords_to_var(n: usize, r: usize, c: usize, d: usize) -> isize {
+    (r * n * n + c * n + (d - 1) + 1) as isize
+}
+
+fn var_to_coords(n: usize, var: usize) -> (usize, usize, usize) {
+    let zero_based_var = var - 1;
+    let r = zero_based_var / (n * n);
+    let c = (zero_based_var / n) % n;
+    let d = (zero_based_var % n) + 1;
+    (r, c, d)
+}
+
+/// Generates the CNF clauses for a Sudoku puzzle: each cell gets an at-least-one plus pairwise
+/// at-most-one clause over its `n` candidate digits, and each row, column and
+/// `box_size x box_size` block likewise gets an at-least-one plus pairwise at-most-one clause
+/// per digit; unit clauses pin the puzzle's givens.
+pub fn generate_clauses(initial_grid: &SudokuGrid) -> Vec<Vec<isize>> {
+    let mut clauses = Vec::new();
+    let n = initial_grid.n();
+    let box_size = initial_grid.box_size();
+
+    // --- Each cell contains at least one digit ---
+    for r in 0..n {
+        for c in 0..n {
+            clauses.push((1..=n).map(|d| coords_to_var(n, r, c, d)).collect());
+        }
+    }
+
+    // --- Each cell contains at most one digit ---
+    for r in 0..n {
+        for c in 0..n {
+            let vars: Vec<isize> = (1..=n).map(|d| coords_to_var(n, r, c, d)).collect();
+            crate::at_most_one_pairwise(&vars, &mut clauses);
+        }
+    }
+
+    // --- Each digit appears at most once per row/column/box ---
+    let rows: Vec<Vec<(usize, usize)>> =
+        (0..n).map(|r| (0..n).map(|c| (r, c)).collect()).collect();
+    let cols: Vec<Vec<(usize, usize)>> =
+        (0..n).map(|c| (0..n).map(|r| (r, c)).collect()).collect();
+    let boxes: Vec<Vec<(usize, usize)>> = (0..box_size)
+        .flat_map(|br| (0..box_size).map(move |bc| (br, bc)))
+        .map(|(br, bc)| {
+            (0..box_size)
+                .flat_map(|ro| (0..box_size).map(move |co| (ro, co)))
+                .map(|(ro, co)| (br * box_size + ro, bc * box_size + co))
+                .collect()
+        })
+        .collect();
+
+    for region in rows.iter().chain(cols.iter()).chain(boxes.iter()) {
+        for d in 1..=n {
+            let vars: Vec<isize> = region
+                .iter()
+                .map(|&(r, c)| coords_to_var(n, r, c, d))
+                .collect();
+            crate::at_most_one_pairwise(&vars, &mut clauses);
+        }
+    }
+
+    // --- Unit clauses for the puzzle's own givens ---
+    for r in 0..n {
+        for c in 0..n {
+            let d = initial_grid.get(r, c);
+            if d != 0 {
+                clauses.push(vec![coords_to_var(n, r, c, d as usize)]);
+            }
+        }
+    }
+
+    clauses
+}
+
+fn decode_solution(model: &[Lit], box_size: usize) -> SudokuGrid {
+    let mut grid = SudokuGrid::empty(box_size);
+    let n = grid.n();
+    let max_cell_var = n * n * n;
+    for &lit in model.iter().filter(|l| l.is_positive()) {
+        let var = lit.var().to_dimacs() as usize;
+        if var <= max_cell_var {
+            let (r, c, d) = var_to_coords(n, var);
+            grid.set(r, c, d as u16);
+        }
+    }
+    grid
+}
+
+/// Solves `grid`, returning the first solution found (or `None` if it has no solution), using
+/// the default (`Sat`) engine.
+pub fn solve_sudoku(grid: &SudokuGrid) -> Result<Option<SudokuGrid>> {
+    solve_sudoku_with(grid, crate::Engine::default())
+}
+
+/// Same as `solve_sudoku`, but lets the caller pick which solving engine to use.
+pub fn solve_sudoku_with(grid: &SudokuGrid, engine: crate::Engine) -> Result<Option<SudokuGrid>> {
+    let clauses = generate_clauses(grid);
+    let box_size = grid.box_size();
+    Ok(crate::find_all_solutions_via(&clauses, engine)?
+        .next()
+        .map(|model| decode_solution(&model, box_size)))
+}
+
+/// Finds every solution to `grid`. A well-formed puzzle should have exactly one. Uses the
+/// default (`Sat`) engine.
+pub fn find_all_solutions(grid: &SudokuGrid) -> Result<Vec<SudokuGrid>> {
+    find_all_solutions_with(grid, crate::Engine::default())
+}
+
+/// Same as `find_all_solutions`, but lets the caller pick which solving engine to use.
+pub fn find_all_solutions_with(grid: &SudokuGrid, engine: crate::Engine) -> Result<Vec<SudokuGrid>> {
+    let clauses = generate_clauses(grid);
+    let box_size = grid.box_size();
+    Ok(crate::find_all_solutions_via(&clauses, engine)?
+        .map(|model| decode_solution(&model, box_size))
+        .collect())
+}
+
+/// Solves an empty `box_size^2 x box_size^2` grid and returns the first full valid solution
+/// found.
+fn full_solution(box_size: usize) -> SudokuGrid {
+    let empty = SudokuGrid::empty(box_size);
+    let clauses = generate_clauses(&empty);
+    let model = crate::find_all_solutions(&clauses)
+        .unwrap()
+        .next()
+        .expect("an empty Sudoku grid always has a solution");
+    decode_solution(&model, box_size)
+}
+
+/// Randomizes a full solution via the symmetries that preserve every row/column/box
+/// constraint: relabeling the digits, permuting rows within a band (and the bands themselves),
+/// and permuting columns within a stack (and the stacks themselves).
+fn permute_full_solution(grid: &SudokuGrid) -> SudokuGrid {
+    let box_size = grid.box_size();
+    let mut rng = rand::rng();
+
+    let mut digit_map: Vec<u16> = (1..=grid.n() as u16).collect();
+    digit_map.shuffle(&mut rng);
+
+    let permuted_group = |rng: &mut rand::rngs::ThreadRng| -> Vec<usize> {
+        let mut bands: Vec<Vec<usize>> = (0..box_size)
+            .map(|band| {
+                let mut indices: Vec<usize> = (band * box_size..(band + 1) * box_size).collect();
+                indices.shuffle(rng);
+                indices
+            })
+            .collect();
+        bands.shuffle(rng);
+        bands.into_iter().flatten().collect()
+    };
+    let row_order = permuted_group(&mut rng);
+    let col_order = permuted_group(&mut rng);
+
+    let mut permuted = SudokuGrid::empty(box_size);
+    for (new_r, &old_r) in row_order.iter().enumerate() {
+        for (new_c, &old_c) in col_order.iter().enumerate() {
+            let old_digit = grid.get(old_r, old_c);
+            let new_digit = if old_digit == 0 {
+                0
+            } else {
+                digit_map[(old_digit - 1) as usize]
+            };
+            permuted.set(new_r, new_c, new_digit);
+        }
+    }
+    permuted
+}
+
+/// The number of non-empty cells in the grid.
+pub fn clue_count(grid: &SudokuGrid) -> usize {
+    grid.cells.iter().filter(|&&d| d != 0).count()
+}
+
+/// Generates a Sudoku puzzle with exactly one solution, retaining as few clues as the given
+/// `difficulty` allows without losing uniqueness: starts from a random full solution (a solved
+/// grid with its digit labels and row/column bands shuffled), then greedily blanks out cells
+/// one at a time, backing out any removal that would break uniqueness.
+pub fn generate_puzzle(box_size: usize, difficulty: Difficulty) -> SudokuGrid {
+    let mut grid = permute_full_solution(&full_solution(box_size));
+    let n = grid.n();
+
+    let mut cells: Vec<(usize, usize)> =
+        (0..n).flat_map(|r| (0..n).map(move |c| (r, c))).collect();
+    cells.shuffle(&mut rand::rng());
+
+    let min_clues = difficulty.min_clues(n * n);
+    let mut num_clues = n * n;
+
+    for (r, c) in cells {
+        if num_clues <= min_clues {
+            break;
+        }
+
+        let removed = grid.get(r, c);
+        grid.set(r, c, 0);
+
+        let clauses = generate_clauses(&grid);
+        if has_unique_solution(&clauses) {
+            num_clues -= 1;
+        } else {
+            grid.set(r, c, removed);
+        }
+    }
+
+    grid
+}
+
+/// The exact DIMACS variable count for a puzzle of this `box_size`: every `(r, c, d)` triple
+/// gets its own variable, `n^3` of them, since the at-most-one constraints above are all
+/// encoded pairwise with no auxiliary variables.
+pub fn num_vars(box_size: usize) -> usize {
+    let n = box_size * box_size;
+    n * n * n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_puzzle_has_unique_solution_and_respects_difficulty() {
+        let grid = generate_puzzle(3, Difficulty::Medium);
+        assert_eq!(find_all_solutions(&grid).unwrap().len(), 1);
+        assert!(clue_count(&grid) >= Difficulty::Medium.min_clues(grid.n() * grid.n()));
+    }
+
+    #[test]
+    fn test_bundled_puzzles_have_unique_solutions() {
+        for puzzle in [puzzle_easy(), puzzle_harder(), puzzle_hard()] {
+            let solutions = find_all_solutions(&puzzle).unwrap();
+            assert_eq!(solutions.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_parse_compact_round_trips_bundled_puzzle() {
+        let text = "530070000\
+                     600195000\
+                     098000060\
+                     800060003\
+                     400803001\
+                     700020006\
+                     060000280\
+                     000419005\
+                     000080079";
+        assert_eq!(SudokuGrid::parse(text).unwrap(), puzzle_easy());
+    }
+
+    #[test]
+    fn test_parse_rejects_inconsistent_givens() {
+        let text = "550070000\
+                     600195000\
+                     098000060\
+                     800060003\
+                     400803001\
+                     700020006\
+                     060000280\
+                     000419005\
+                     000080079";
+        assert!(matches!(
+            SudokuGrid::parse(text),
+            Err(SudokuParseError::Inconsistent(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_triples() {
+        let text = "9,9\n0,0,1\n0,1,2";
+        let grid = SudokuGrid::parse(text).unwrap();
+        assert_eq!(grid.get(0, 0), 1);
+        assert_eq!(grid.get(0, 1), 2);
+        assert_eq!(grid.get(0, 2), 0);
+    }
+
+    #[test]
+    fn test_generate_clauses_variable_count_matches_num_vars() {
+        let grid = puzzle_easy();
+        let clauses = generate_clauses(&grid);
+        let max_var = clauses
+            .iter()
+            .flat_map(|c| c.iter())
+            .map(|&lit| lit.unsigned_abs())
+            .max()
+            .unwrap();
+        assert_eq!(max_var, num_vars(grid.box_size()));
+    }
+
+    #[test]
+    fn test_solves_a_4x4_puzzle() {
+        // box_size = 2, a 4x4 grid.
+        let text = "4,4\n0,0,1\n1,2,1\n2,1,1\n3,3,1";
+        let grid = SudokuGrid::parse(text).unwrap();
+        let solution = solve_sudoku(&grid).unwrap().expect("should be solvable");
+        assert_eq!(solution.box_size(), 2);
+        assert_eq!(solution.get(0, 0), 1);
+    }
+}
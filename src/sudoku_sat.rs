@@ -1,3 +1,5 @@
+use crate::AtMostOne;
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt;
 use std::fs;
@@ -5,9 +7,17 @@ use std::io;
 use std::path::Path;
 use varisat::Lit;
 
-const N: usize = 9; // The dimension of the grid (9x9)
-pub struct SudokuGrid([[u8; N]; N]); // 0 represents an empty cell.
-const BOX_SIZE: usize = 3; // The dimension of a sub-box (3x3)
+/// A Sudoku (or Latin-square variant) grid of dimension `box_size^2 x box_size^2`, holding
+/// values `1..=box_size^2` (0 for an empty cell), stored row-major.
+///
+/// Unlike a fixed 9x9 board, `box_size` is a runtime property of each grid rather than a
+/// compile-time constant, so the same type and encoder handle 9x9, 16x16, 25x25 and larger
+/// boards alike.
+#[derive(Clone)]
+pub struct SudokuGrid {
+    box_size: usize,
+    cells: Vec<u16>,
+}
 
 // Error type for parsing
 #[derive(Debug)]
@@ -40,45 +50,153 @@ impl fmt::Display for SudokuParseError {
     }
 }
 
+/// Given a cell count, infers the box size `b` such that the count is exactly `(b*b)^2`
+/// cells — i.e. a `b*b x b*b` grid. Returns `None` if the count doesn't form such a square.
+fn infer_box_size(cell_count: usize) -> Option<usize> {
+    let n = (cell_count as f64).sqrt().round() as usize;
+    if n * n != cell_count || n == 0 {
+        return None;
+    }
+    let b = (n as f64).sqrt().round() as usize;
+    if b * b != n { None } else { Some(b) }
+}
+
+/// Parses a `rows,cols` dimension header line, as used by the coordinate-triple format.
+fn parse_dimension_header(line: &str) -> Option<(usize, usize)> {
+    let (rows, cols) = line.split_once(',')?;
+    Some((rows.trim().parse().ok()?, cols.trim().parse().ok()?))
+}
+
+/// Parses a single cell's text representation: `.` or `0` for empty, a decimal number for any
+/// dimension, or (for a single character) a hex-style digit — `A` is 10, `B` is 11, and so on
+/// — for board dimensions above 9.
+fn parse_cell_token(token: &str) -> Result<u16, SudokuParseError> {
+    if token == "." || token == "0" {
+        return Ok(0);
+    }
+    if let Ok(n) = token.parse::<u16>() {
+        return Ok(n);
+    }
+    if let Some(c) = token.chars().next().filter(|_| token.chars().count() == 1) {
+        let c = c.to_ascii_uppercase();
+        if c.is_ascii_uppercase() {
+            return Ok((c as u16) - (b'A' as u16) + 10);
+        }
+    }
+    Err(SudokuParseError::InvalidFormat(format!(
+        "Invalid cell value '{token}'"
+    )))
+}
+
 impl SudokuGrid {
-    /// Parse a Sudoku puzzle from text
-    /// Accepts formats with or without spaces, using 0 or . for empty cells
+    /// Parse a Sudoku puzzle from text.
+    ///
+    /// Accepts three layouts, auto-detected: the coordinate-triple format (a `rows,cols`
+    /// header followed by one `row,col,value` line per given), cells separated by whitespace
+    /// (needed for multi-character or hex-style values once the board grows past 9x9), or the
+    /// compact one-character-per-cell layout used by classic 9x9 puzzles (`.`/`0` for empty,
+    /// `1`-`9` for a digit, and `A`-`Z` beyond that). Outside of the coordinate-triple header,
+    /// the dimension is inferred from however many cells were parsed.
     pub fn from_text(text: &str) -> Result<Self, SudokuParseError> {
-        let mut grid = [[0u8; N]; N];
+        let first_line = text.lines().map(str::trim).find(|l| !l.is_empty());
+        let looks_like_triples = first_line
+            .and_then(parse_dimension_header)
+            .is_some_and(|(rows, cols)| rows == cols && infer_box_size(rows * cols).is_some());
+        if looks_like_triples {
+            return Self::from_triples(text);
+        }
+
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+        if tokens.len() > 1 && infer_box_size(tokens.len()).is_some() {
+            let values = tokens
+                .iter()
+                .map(|t| parse_cell_token(t))
+                .collect::<Result<Vec<u16>, _>>()?;
+            return Self::from_values(values);
+        }
 
-        // Remove all whitespace and collect digits/dots
-        let chars: Vec<char> = text
+        let values = text
             .chars()
-            .filter(|&c| c.is_ascii_digit() || c == '.')
-            .collect();
+            .filter(|c| !c.is_whitespace())
+            .map(|c| parse_cell_token(&c.to_string()))
+            .collect::<Result<Vec<u16>, _>>()?;
+        Self::from_values(values)
+    }
 
-        let expected_count = N * N;
-        if chars.len() != expected_count {
+    /// Parses the coordinate-triple format: a `rows,cols` header line, followed by one
+    /// `row,col,value` line per given cell (0-based coordinates, 1-based value, 0 meaning
+    /// empty). Cells not listed default to empty. Every unlisted cell, and the header itself,
+    /// is implicit — only the givens need a line.
+    pub fn from_triples(text: &str) -> Result<Self, SudokuParseError> {
+        let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+        let header = lines
+            .next()
+            .ok_or_else(|| SudokuParseError::InvalidFormat("missing dimension header".into()))?;
+        let (rows, cols) = parse_dimension_header(header).ok_or_else(|| {
+            SudokuParseError::InvalidFormat(format!("invalid dimension header '{header}'"))
+        })?;
+        if rows != cols {
             return Err(SudokuParseError::InvalidFormat(format!(
-                "Expected {expected_count} cells, found {}",
-                chars.len()
+                "grid must be square, got {rows}x{cols}"
             )));
         }
-
-        for (idx, &ch) in chars.iter().enumerate() {
-            let row = idx / N;
-            let col = idx % N;
-
-            let num = match ch {
-                '.' | '0' => 0,
-                '1'..='9' => ch.to_digit(10).unwrap() as u8,
-                _ => {
-                    return Err(SudokuParseError::InvalidFormat(format!(
-                        "Invalid character '{ch}' at position {}",
-                        idx + 1
-                    )));
-                }
+        let n = rows;
+        let box_size = infer_box_size(n * n).ok_or_else(|| {
+            SudokuParseError::InvalidFormat(format!(
+                "{n}x{n} is not a box_size^2 x box_size^2 grid"
+            ))
+        })?;
+
+        let mut grid = SudokuGrid::empty(box_size);
+        for line in lines {
+            let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [r, c, v] = parts.as_slice() else {
+                return Err(SudokuParseError::InvalidFormat(format!(
+                    "expected 'row,col,value', got '{line}'"
+                )));
             };
-
-            grid[row][col] = num;
+            let parse_usize = |s: &str, what: &str| {
+                s.parse::<usize>().map_err(|_| {
+                    SudokuParseError::InvalidFormat(format!("invalid {what} in '{line}'"))
+                })
+            };
+            let r = parse_usize(r, "row")?;
+            let c = parse_usize(c, "column")?;
+            let v: u16 = v
+                .parse()
+                .map_err(|_| SudokuParseError::InvalidFormat(format!("invalid value in '{line}'")))?;
+            if r >= n || c >= n {
+                return Err(SudokuParseError::InvalidFormat(format!(
+                    "coordinate ({r}, {c}) is outside the {n}x{n} grid"
+                )));
+            }
+            if v as usize > n {
+                return Err(SudokuParseError::InvalidFormat(format!(
+                    "value {v} exceeds grid dimension {n}"
+                )));
+            }
+            grid.set(r, c, v);
         }
+        Ok(grid)
+    }
 
-        Ok(SudokuGrid(grid))
+    fn from_values(values: Vec<u16>) -> Result<Self, SudokuParseError> {
+        let box_size = infer_box_size(values.len()).ok_or_else(|| {
+            SudokuParseError::InvalidFormat(format!(
+                "{} cells do not form a box_size^2 x box_size^2 grid",
+                values.len()
+            ))
+        })?;
+        let n = box_size * box_size;
+        if let Some(&bad) = values.iter().find(|&&v| v as usize > n) {
+            return Err(SudokuParseError::InvalidFormat(format!(
+                "Cell value {bad} exceeds grid dimension {n}"
+            )));
+        }
+        Ok(SudokuGrid {
+            box_size,
+            cells: values,
+        })
     }
 
     /// Read a Sudoku puzzle from a file
@@ -86,110 +204,327 @@ impl SudokuGrid {
         let content = fs::read_to_string(path)?;
         Self::from_text(&content)
     }
+
+    /// The dimension of a sub-box (e.g. 3 for a classic 9x9 grid).
+    pub fn box_size(&self) -> usize {
+        self.box_size
+    }
+
+    /// The dimension of the grid (`box_size^2`, e.g. 9 for a classic 9x9 grid).
+    pub fn n(&self) -> usize {
+        self.box_size * self.box_size
+    }
+
+    /// The digit at `(r, c)`, or 0 if the cell is empty.
+    pub fn get(&self, r: usize, c: usize) -> u16 {
+        self.cells[r * self.n() + c]
+    }
+
+    /// Sets the digit at `(r, c)` (0 clears the cell).
+    pub fn set(&mut self, r: usize, c: usize, digit: u16) {
+        let n = self.n();
+        self.cells[r * n + c] = digit;
+    }
+
+    /// An empty `box_size^2 x box_size^2` grid with no givens.
+    pub fn empty(box_size: usize) -> Self {
+        let n = box_size * box_size;
+        SudokuGrid {
+            box_size,
+            cells: vec![0; n * n],
+        }
+    }
 }
 
-/// Helper to map a 0-indexed (row, col, digit) to a 1-indexed DIMACS variable number.
-/// A variable is true if cell (r, c) contains digit d.
-/// Digits are 1-9.
-fn coords_to_var(r: usize, c: usize, d: usize) -> isize {
-    // r: 0-8, c: 0-8, d: 1-9
-    // We map d from 1-9 to a 0-8 index for calculation.
-    (r * N * N + c * N + (d - 1) + 1) as isize
+/// Helper to map a 0-indexed (row, col, digit) to a 1-indexed DIMACS variable number, for a
+/// grid of dimension `n`. A variable is true if cell (r, c) contains digit d. Digits are
+/// `1..=n`.
+fn coords_to_var(n: usize, r: usize, c: usize, d: usize) -> isize {
+    (r * n * n + c * n + (d - 1) + 1) as isize
 }
 
-/// Helper to map a 1-indexed DIMACS variable number back to 0-indexed (row, col, digit).
-fn var_to_coords(var: usize) -> (usize, usize, usize) {
+/// Helper to map a 1-indexed DIMACS variable number back to 0-indexed (row, col, digit), for
+/// a grid of dimension `n`.
+fn var_to_coords(n: usize, var: usize) -> (usize, usize, usize) {
     let zero_based_var = var - 1;
-    let r = zero_based_var / (N * N);
-    let c = (zero_based_var / N) % N;
-    let d = (zero_based_var % N) + 1; // Convert back to 1-9 digit
+    let r = zero_based_var / (n * n);
+    let c = (zero_based_var / n) % n;
+    let d = (zero_based_var % n) + 1; // Convert back to a 1..=n digit
     (r, c, d)
 }
 
-/// Generates the CNF clauses for a Sudoku puzzle.
-pub fn generate_clauses(initial_grid: &SudokuGrid) -> Vec<Vec<isize>> {
-    let mut clauses = Vec::new();
+/// A group of cells that must each hold a different digit: a row, column, box, or any variant
+/// region (diagonal, windoku box, ...). Every `Constraint` below is just a different way of
+/// producing a list of `Region`s and feeding them through `emit_region_clauses`, which is what
+/// lets new Sudoku variants be added as plain data rather than bespoke clause-emission code.
+pub type Region = Vec<(usize, usize)>;
+
+/// A group of cells that must each hold a different digit (a row, column, box, or variant
+/// region). Implementations only need to emit the "at most one per digit" clauses (via
+/// `emit_region_clauses`, so they pick up whichever `AtMostOne` strategy the caller chose);
+/// "at least one digit per cell" is already covered once for the whole grid. `grid` is passed
+/// in so implementations can size themselves to the grid's dimension and box size.
+pub trait Constraint {
+    fn emit_clauses(
+        &self,
+        grid: &SudokuGrid,
+        var: &dyn Fn(usize, usize, usize) -> isize,
+        next_var: &mut usize,
+        strategy: AtMostOne,
+        clauses: &mut Vec<Vec<isize>>,
+    );
+}
 
-    // --- CONSTRAINT 1: Each cell contains at least one digit ---
-    // For each cell (r, c), add the clause (x_r,c,1 OR x_r,c,2 OR ... OR x_r,c,9)
-    for r in 0..N {
-        for c in 0..N {
-            clauses.push((1..=N).map(|d| coords_to_var(r, c, d)).collect());
+/// Emits "at most one digit" clauses for every digit `1..=n` over a fixed list of regions
+/// (each region a list of cells that must not repeat a digit), using `strategy`. Pairwise is
+/// fine for a 9x9 board's 3-cell-wide regions, but a naive pairwise row/column/box on a
+/// 400x400 board is the quadratic blow-up `AtMostOne` exists to avoid.
+fn emit_region_clauses(
+    regions: &[Region],
+    n: usize,
+    var: &dyn Fn(usize, usize, usize) -> isize,
+    next_var: &mut usize,
+    strategy: AtMostOne,
+    clauses: &mut Vec<Vec<isize>>,
+) {
+    for region in regions {
+        for d in 1..=n {
+            let vars: Vec<isize> = region.iter().map(|&(r, c)| var(r, c, d)).collect();
+            strategy.encode(&vars, next_var, clauses);
         }
     }
+}
 
-    // --- CONSTRAINT 2: Each cell contains at most one digit ---
-    // For each cell (r, c) and each pair of digits d1, d2: (-x_r,c,d1 OR -x_r,c,d2)
-    for r in 0..N {
-        for c in 0..N {
-            for d1 in 1..=N {
-                for d2 in (d1 + 1)..=N {
-                    clauses.push(vec![-coords_to_var(r, c, d1), -coords_to_var(r, c, d2)]);
+/// Each digit appears at most once in each row.
+pub struct RowConstraint;
+
+impl Constraint for RowConstraint {
+    fn emit_clauses(
+        &self,
+        grid: &SudokuGrid,
+        var: &dyn Fn(usize, usize, usize) -> isize,
+        next_var: &mut usize,
+        strategy: AtMostOne,
+        clauses: &mut Vec<Vec<isize>>,
+    ) {
+        let n = grid.n();
+        let regions: Vec<Region> =
+            (0..n).map(|r| (0..n).map(|c| (r, c)).collect()).collect();
+        emit_region_clauses(&regions, n, var, next_var, strategy, clauses);
+    }
+}
+
+/// Each digit appears at most once in each column.
+pub struct ColumnConstraint;
+
+impl Constraint for ColumnConstraint {
+    fn emit_clauses(
+        &self,
+        grid: &SudokuGrid,
+        var: &dyn Fn(usize, usize, usize) -> isize,
+        next_var: &mut usize,
+        strategy: AtMostOne,
+        clauses: &mut Vec<Vec<isize>>,
+    ) {
+        let n = grid.n();
+        let regions: Vec<Region> =
+            (0..n).map(|c| (0..n).map(|r| (r, c)).collect()).collect();
+        emit_region_clauses(&regions, n, var, next_var, strategy, clauses);
+    }
+}
+
+/// Each digit appears at most once in each `box_size x box_size` box.
+pub struct BoxConstraint;
+
+impl Constraint for BoxConstraint {
+    fn emit_clauses(
+        &self,
+        grid: &SudokuGrid,
+        var: &dyn Fn(usize, usize, usize) -> isize,
+        next_var: &mut usize,
+        strategy: AtMostOne,
+        clauses: &mut Vec<Vec<isize>>,
+    ) {
+        let b = grid.box_size();
+        let mut regions = Vec::new();
+        for br in 0..b {
+            for bc in 0..b {
+                let mut cells_in_box = Vec::new();
+                for r_offset in 0..b {
+                    for c_offset in 0..b {
+                        cells_in_box.push((br * b + r_offset, bc * b + c_offset));
+                    }
                 }
+                regions.push(cells_in_box);
             }
         }
+        emit_region_clauses(&regions, grid.n(), var, next_var, strategy, clauses);
+    }
+}
+
+/// X-Sudoku / diagonal variant: both main diagonals must each contain every digit once.
+pub struct DiagonalConstraint;
+
+impl Constraint for DiagonalConstraint {
+    fn emit_clauses(
+        &self,
+        grid: &SudokuGrid,
+        var: &dyn Fn(usize, usize, usize) -> isize,
+        next_var: &mut usize,
+        strategy: AtMostOne,
+        clauses: &mut Vec<Vec<isize>>,
+    ) {
+        let n = grid.n();
+        let main_diagonal: Region = (0..n).map(|i| (i, i)).collect();
+        let anti_diagonal: Region = (0..n).map(|i| (i, n - 1 - i)).collect();
+        emit_region_clauses(
+            &[main_diagonal, anti_diagonal],
+            n,
+            var,
+            next_var,
+            strategy,
+            clauses,
+        );
     }
+}
+
+/// Hyper/Windoku variant: four extra `box_size x box_size` regions offset one cell in from
+/// the box grid's outer edge, e.g. top-left corners at (1,1), (1,5), (5,1) and (5,5) on a
+/// classic 9x9 board. No-ops on grids too small for the four regions to sit clear of each
+/// other.
+pub struct WindokuConstraint;
+
+impl Constraint for WindokuConstraint {
+    fn emit_clauses(
+        &self,
+        grid: &SudokuGrid,
+        var: &dyn Fn(usize, usize, usize) -> isize,
+        next_var: &mut usize,
+        strategy: AtMostOne,
+        clauses: &mut Vec<Vec<isize>>,
+    ) {
+        let b = grid.box_size();
+        let n = grid.n();
+        if n < 2 * b + 2 {
+            return;
+        }
 
-    // --- CONSTRAINT 3: Each digit appears at most once in each row ---
-    // For each row r, digit d, and pair of columns c1, c2: (-x_r,c1,d OR -x_r,c2,d)
-    for r in 0..N {
-        for d in 1..=N {
-            for c1 in 0..N {
-                for c2 in (c1 + 1)..N {
-                    clauses.push(vec![-coords_to_var(r, c1, d), -coords_to_var(r, c2, d)]);
+        let offsets: HashSet<usize> = [1, n - b - 1].into_iter().collect();
+        let mut regions = Vec::new();
+        for &br in &offsets {
+            for &bc in &offsets {
+                let mut cells_in_region = Vec::new();
+                for r_offset in 0..b {
+                    for c_offset in 0..b {
+                        cells_in_region.push((br + r_offset, bc + c_offset));
+                    }
                 }
+                regions.push(cells_in_region);
             }
         }
+        emit_region_clauses(&regions, n, var, next_var, strategy, clauses);
     }
+}
 
-    // --- CONSTRAINT 4: Each digit appears at most once in each column ---
-    // For each column c, digit d, and pair of rows r1, r2: (-x_r1,c,d OR -x_r2,c,d)
-    for c in 0..N {
-        for d in 1..=N {
-            for r1 in 0..N {
-                for r2 in (r1 + 1)..N {
-                    clauses.push(vec![-coords_to_var(r1, c, d), -coords_to_var(r2, c, d)]);
+/// Anti-knight variant: no two cells a knight's move apart may share a digit. Each exclusion
+/// is a single pair, so it is emitted as a plain pairwise clause regardless of `strategy` —
+/// there's no group to grow into a quadratic blow-up.
+pub struct AntiKnightConstraint;
+
+impl Constraint for AntiKnightConstraint {
+    fn emit_clauses(
+        &self,
+        grid: &SudokuGrid,
+        var: &dyn Fn(usize, usize, usize) -> isize,
+        _next_var: &mut usize,
+        _strategy: AtMostOne,
+        clauses: &mut Vec<Vec<isize>>,
+    ) {
+        const KNIGHT_OFFSETS: [(isize, isize); 4] = [(1, 2), (2, 1), (1, -2), (2, -1)];
+        let n = grid.n();
+        for r in 0..n {
+            for c in 0..n {
+                for &(dr, dc) in &KNIGHT_OFFSETS {
+                    let r2 = r as isize + dr;
+                    let c2 = c as isize + dc;
+                    if r2 >= 0 && r2 < n as isize && c2 >= 0 && c2 < n as isize {
+                        let (r2, c2) = (r2 as usize, c2 as usize);
+                        for d in 1..=n {
+                            clauses.push(vec![-var(r, c, d), -var(r2, c2, d)]);
+                        }
+                    }
                 }
             }
         }
     }
+}
 
-    // --- CONSTRAINT 5: Each digit appears at most once in each 3x3 box ---
-    for d in 1..=N {
-        for br in 0..BOX_SIZE {
-            // Box row
-            for bc in 0..BOX_SIZE {
-                // Box col
-                let mut cells_in_box = Vec::new();
-                for r_offset in 0..BOX_SIZE {
-                    for c_offset in 0..BOX_SIZE {
-                        let r = br * BOX_SIZE + r_offset;
-                        let c = bc * BOX_SIZE + c_offset;
-                        cells_in_box.push((r, c));
-                    }
-                }
+/// The three classic Sudoku region families.
+pub fn standard_constraints() -> Vec<Box<dyn Constraint>> {
+    vec![
+        Box::new(RowConstraint),
+        Box::new(ColumnConstraint),
+        Box::new(BoxConstraint),
+    ]
+}
 
-                for i in 0..cells_in_box.len() {
-                    for j in (i + 1)..cells_in_box.len() {
-                        let (r1, c1) = cells_in_box[i];
-                        let (r2, c2) = cells_in_box[j];
-                        clauses.push(vec![-coords_to_var(r1, c1, d), -coords_to_var(r2, c2, d)]);
-                    }
-                }
-            }
+/// Generates the CNF clauses for a Sudoku puzzle (or a Latin-square variant) by composing
+/// the given list of region `Constraint`s over the standard cell variables, using the default
+/// `AtMostOne::Auto` cardinality strategy. See `generate_clauses_with` to pick a strategy
+/// explicitly, e.g. `Sequential` or `Commander` for very large grids.
+pub fn generate_clauses(
+    initial_grid: &SudokuGrid,
+    constraints: &[Box<dyn Constraint>],
+) -> Vec<Vec<isize>> {
+    generate_clauses_with(initial_grid, constraints, AtMostOne::default())
+}
+
+/// Same as `generate_clauses`, but lets the caller pick which `AtMostOne` cardinality encoding
+/// every "at most one digit per cell/region" group uses — important once the grid is large
+/// enough that the naive pairwise encoding's O(k^2) clause count dominates (e.g. a 400x400
+/// Sudoku's rows/columns/boxes).
+pub fn generate_clauses_with(
+    initial_grid: &SudokuGrid,
+    constraints: &[Box<dyn Constraint>],
+    strategy: AtMostOne,
+) -> Vec<Vec<isize>> {
+    let mut clauses = Vec::new();
+    let n = initial_grid.n();
+    let var = move |r: usize, c: usize, d: usize| coords_to_var(n, r, c, d);
+
+    // --- Each cell contains at least one digit ---
+    // For each cell (r, c), add the clause (x_r,c,1 OR x_r,c,2 OR ... OR x_r,c,n)
+    for r in 0..n {
+        for c in 0..n {
+            clauses.push((1..=n).map(|d| coords_to_var(n, r, c, d)).collect());
         }
     }
 
-    // Note: The "at least one" constraint for rows, columns, and boxes is implicitly satisfied
-    // by the combination of "each cell has a number" and "at most one of each number per region".
+    // --- Each cell contains at most one digit ---
+    // Auxiliary variables for the sequential/commander encodings (if used) start past the
+    // puzzle's own 1..=n*n*n variable range.
+    let mut next_var = n * n * n + 1;
+    for r in 0..n {
+        for c in 0..n {
+            let vars: Vec<isize> = (1..=n).map(|d| coords_to_var(n, r, c, d)).collect();
+            strategy.encode(&vars, &mut next_var, &mut clauses);
+        }
+    }
 
-    // --- CONSTRAINT 6: Add clauses for the pre-filled numbers (the puzzle seed) ---
-    for r in 0..N {
-        for c in 0..N {
-            if initial_grid.0[r][c] != 0 {
-                let d = initial_grid.0[r][c] as usize;
+    // --- Region constraints (rows/columns/boxes plus any variants) ---
+    // Note: The "at least one" constraint for each region is implicitly satisfied by the
+    // combination of "each cell has a number" and "at most one of each number per region".
+    for constraint in constraints {
+        constraint.emit_clauses(initial_grid, &var, &mut next_var, strategy, &mut clauses);
+    }
+
+    // --- Add clauses for the pre-filled numbers (the puzzle seed) ---
+    for r in 0..n {
+        for c in 0..n {
+            let d = initial_grid.get(r, c);
+            if d != 0 {
                 // This is a unit clause, forcing the variable to be true.
-                clauses.push(vec![coords_to_var(r, c, d)]);
+                clauses.push(vec![coords_to_var(n, r, c, d as usize)]);
             }
         }
     }
@@ -197,43 +532,136 @@ pub fn generate_clauses(initial_grid: &SudokuGrid) -> Vec<Vec<isize>> {
     clauses
 }
 
-pub fn decode_solution(model: &[Lit]) -> SudokuGrid {
-    let mut current_solution = SudokuGrid([[0; N]; N]); // 0 represents an empty cell.
+/// Decodes a SAT model into a `box_size`-dimensioned `SudokuGrid`. `box_size` must match the
+/// grid the clauses were generated from.
+pub fn decode_solution(model: &[Lit], box_size: usize) -> SudokuGrid {
+    let mut current_solution = SudokuGrid::empty(box_size);
+    let n = current_solution.n();
+    let max_cell_var = n * n * n;
     for &lit in model.iter() {
         if lit.is_positive() {
-            let (r, c, d) = var_to_coords(lit.var().to_dimacs() as usize);
-            current_solution.0[r][c] = d as u8;
+            let var = lit.var().to_dimacs() as usize;
+            // Ignore sequential-encoding auxiliary variables, which live past n*n*n.
+            if var <= max_cell_var {
+                let (r, c, d) = var_to_coords(n, var);
+                current_solution.set(r, c, d as u16);
+            }
         }
     }
     current_solution
 }
 
+/// The result of checking whether a puzzle has exactly one solution, from `check_uniqueness`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Uniqueness {
+    /// Exactly one solution exists.
+    Unique,
+    /// More than one solution exists; lists every cell where the first two solutions found
+    /// disagree.
+    NotUnique { differing_cells: Vec<(usize, usize)> },
+    /// The puzzle's givens contradict the constraints, so it has no solution at all.
+    Unsatisfiable,
+}
+
+/// Checks whether `initial_grid` has exactly one solution under `constraints`, by solving once,
+/// then blocking that exact assignment (the disjunction of its negated true cell-variables —
+/// the same blocking-clause trick `SolutionIterator` uses internally) and solving again.
+pub fn check_uniqueness(
+    initial_grid: &SudokuGrid,
+    constraints: &[Box<dyn Constraint>],
+) -> Uniqueness {
+    let clauses = generate_clauses(initial_grid, constraints);
+    let Ok(mut solutions) = crate::find_all_solutions(&clauses) else {
+        return Uniqueness::Unsatisfiable;
+    };
+    let Some(first) = solutions.next() else {
+        return Uniqueness::Unsatisfiable;
+    };
+    match solutions.next() {
+        None => Uniqueness::Unique,
+        Some(second) => {
+            let box_size = initial_grid.box_size();
+            let a = decode_solution(&first, box_size);
+            let b = decode_solution(&second, box_size);
+            let n = a.n();
+            let differing_cells = (0..n)
+                .flat_map(|r| (0..n).map(move |c| (r, c)))
+                .filter(|&(r, c)| a.get(r, c) != b.get(r, c))
+                .collect();
+            Uniqueness::NotUnique { differing_cells }
+        }
+    }
+}
+
+/// Finds every given in `initial_grid` that can be blanked out without losing a unique
+/// solution — i.e. every logically redundant clue. Checks clues one at a time, in row-major
+/// order, re-testing uniqueness against the grid with clues already found redundant removed
+/// (so later checks benefit from earlier removals, the same greedy approach `generator`'s
+/// puzzle generation uses).
+pub fn find_redundant_clues(
+    initial_grid: &SudokuGrid,
+    constraints: &[Box<dyn Constraint>],
+) -> Vec<(usize, usize)> {
+    let n = initial_grid.n();
+    let mut grid = initial_grid.clone();
+    let mut redundant = Vec::new();
+
+    for r in 0..n {
+        for c in 0..n {
+            let value = grid.get(r, c);
+            if value == 0 {
+                continue;
+            }
+
+            grid.set(r, c, 0);
+            if check_uniqueness(&grid, constraints) == Uniqueness::Unique {
+                redundant.push((r, c));
+            } else {
+                grid.set(r, c, value);
+            }
+        }
+    }
+
+    redundant
+}
+
 impl fmt::Display for SudokuGrid {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "┌───────┬───────┬───────┐")?;
+        let n = self.n();
+        let b = self.box_size;
+        let cell_width = n.to_string().len();
+
+        let border = |f: &mut fmt::Formatter<'_>, left: &str, mid: &str, right: &str| -> fmt::Result {
+            write!(f, "{left}")?;
+            for bc in 0..b {
+                write!(f, "{}", "─".repeat(b * (cell_width + 1) + 1))?;
+                write!(f, "{}", if bc + 1 < b { mid } else { right })?;
+            }
+            writeln!(f)
+        };
 
-        for (r, row_data) in self.0.iter().enumerate() {
+        border(f, "┌", "┬", "┐")?;
+        for r in 0..n {
             write!(f, "│")?;
-            for (c, &cell_value) in row_data.iter().enumerate() {
-                if c > 0 && c % 3 == 0 {
+            for c in 0..n {
+                if c > 0 && c % b == 0 {
                     write!(f, " │")?;
                 }
-
-                let ch = if cell_value == 0 {
-                    '·'
-                } else {
-                    (b'0' + cell_value) as char
+                let v = self.get(r, c);
+                let ch = match v {
+                    0 => "·".to_string(),
+                    1..=9 => v.to_string(),
+                    10..=35 => ((b'A' + (v - 10) as u8) as char).to_string(),
+                    _ => v.to_string(),
                 };
-                write!(f, " {ch}")?;
+                write!(f, " {ch:>cell_width$}")?;
             }
             writeln!(f, " │")?;
-
-            if r < 8 && (r + 1) % 3 == 0 {
-                writeln!(f, "├───────┼───────┼───────┤")?;
+            if r + 1 < n && (r + 1) % b == 0 {
+                border(f, "├", "┼", "┤")?;
             }
         }
-
-        write!(f, "└───────┴───────┴───────┘")
+        border(f, "└", "┴", "┘")
     }
 }
 
@@ -254,5 +682,111 @@ mod tests {
             ..5.1.3..";
         let result = SudokuGrid::from_text(input);
         assert!(result.is_ok());
+        assert_eq!(result.unwrap().box_size(), 3);
+    }
+
+    #[test]
+    fn test_from_text_tokens_16x16() {
+        // 16x16 needs 256 whitespace-separated tokens; reuse one row's worth of hex digits.
+        let row = "1 2 3 4 5 6 7 8 9 A B C D E F G";
+        let text: String = std::iter::repeat_n(row, 16).collect::<Vec<_>>().join("\n");
+        let grid = SudokuGrid::from_text(&text).unwrap();
+        assert_eq!(grid.box_size(), 4);
+        assert_eq!(grid.get(0, 15), 16);
+    }
+
+    #[test]
+    fn test_from_triples() {
+        let input = "9,9
+            0,2,3
+            0,4,2
+            0,5,6
+            1,0,9
+            1,3,3
+            1,5,5
+            1,8,1";
+        let grid = SudokuGrid::from_text(input).unwrap();
+        assert_eq!(grid.box_size(), 3);
+        assert_eq!(grid.get(0, 2), 3);
+        assert_eq!(grid.get(1, 0), 9);
+        assert_eq!(grid.get(0, 0), 0);
+    }
+
+    #[test]
+    fn test_from_triples_rejects_out_of_range_coordinate() {
+        let input = "9,9\n9,0,1";
+        let result = SudokuGrid::from_text(input);
+        assert!(matches!(result, Err(SudokuParseError::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn test_combined_variant_constraints() {
+        // Variants are just extra `Constraint`s layered on top of the standard ones. Diagonal
+        // (X-Sudoku) and anti-knight together over-constrain a 4x4 grid to the point of being
+        // unsatisfiable, so this combines diagonal with the standard constraints only, and
+        // checks that the result still respects every region at once.
+        let empty = SudokuGrid::empty(2);
+        let mut constraints = standard_constraints();
+        constraints.push(Box::new(DiagonalConstraint));
+        let clauses = generate_clauses(&empty, &constraints);
+
+        let model = crate::find_all_solutions(&clauses)
+            .unwrap()
+            .next()
+            .expect("a 4x4 grid with a diagonal constraint is still solvable");
+        let solution = decode_solution(&model, 2);
+
+        let n = solution.n();
+        let main_diagonal: Vec<u16> = (0..n).map(|i| solution.get(i, i)).collect();
+        let mut sorted = main_diagonal.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (1..=n as u16).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_combined_diagonal_and_anti_knight_constraints_are_unsatisfiable() {
+        // Confirmed unsatisfiable by brute force: a 4x4 grid can't simultaneously satisfy the
+        // standard row/column/box constraints, both main diagonals, and the anti-knight rule.
+        let empty = SudokuGrid::empty(2);
+        let mut constraints = standard_constraints();
+        constraints.push(Box::new(DiagonalConstraint));
+        constraints.push(Box::new(AntiKnightConstraint));
+        let clauses = generate_clauses(&empty, &constraints);
+
+        assert!(crate::find_all_solutions(&clauses).unwrap().next().is_none());
+    }
+
+    #[test]
+    fn test_check_uniqueness_on_empty_grid() {
+        // An empty 4x4 grid has many valid solutions.
+        let empty = SudokuGrid::empty(2);
+        match check_uniqueness(&empty, &standard_constraints()) {
+            Uniqueness::NotUnique { differing_cells } => assert!(!differing_cells.is_empty()),
+            other => panic!("expected NotUnique, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_find_redundant_clues_preserves_uniqueness() {
+        let empty = SudokuGrid::empty(2);
+        let clauses = generate_clauses(&empty, &standard_constraints());
+        let model = crate::find_all_solutions(&clauses).unwrap().next().unwrap();
+        let full = decode_solution(&model, 2);
+        assert_eq!(
+            check_uniqueness(&full, &standard_constraints()),
+            Uniqueness::Unique
+        );
+
+        let redundant = find_redundant_clues(&full, &standard_constraints());
+        assert!(!redundant.is_empty());
+
+        let mut minimized = full.clone();
+        for &(r, c) in &redundant {
+            minimized.set(r, c, 0);
+        }
+        assert_eq!(
+            check_uniqueness(&minimized, &standard_constraints()),
+            Uniqueness::Unique
+        );
     }
 }